@@ -0,0 +1,63 @@
+use bevy::prelude::*;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg32;
+
+use asteroids::collision::broad_phase::{Bounds, Grid, CELL_SIZE};
+
+const COUNTS: [usize; 3] = [100, 300, 1000];
+
+// Bodies spread over a square of side 8000, about the size of the loaded sectors
+fn random_bounds(count: usize) -> Vec<Bounds> {
+    let mut rng = Pcg32::seed_from_u64(count as u64);
+    (0..count)
+        .map(|_| {
+            Bounds::from_center(
+                Vec2::new(
+                    rng.gen_range(-4000.0..4000.0),
+                    rng.gen_range(-4000.0..4000.0),
+                ),
+                Vec2::splat(rng.gen_range(10.0..150.0)),
+            )
+        })
+        .collect()
+}
+
+fn naive(bounds: &[Bounds]) -> Vec<(usize, usize)> {
+    let mut pairs = Vec::new();
+    for i in 0..bounds.len() {
+        for j in i + 1..bounds.len() {
+            if bounds[i].intersects(bounds[j]) {
+                pairs.push((i, j));
+            }
+        }
+    }
+
+    pairs
+}
+
+fn grid(bounds: &[Bounds]) -> Vec<(usize, usize)> {
+    let mut grid = Grid::new(CELL_SIZE);
+    for b in bounds {
+        grid.insert(*b);
+    }
+
+    grid.pairs()
+}
+
+fn benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("broad_phase");
+    for count in COUNTS {
+        let bounds = random_bounds(count);
+        group.bench_with_input(BenchmarkId::new("naive", count), &bounds, |b, bounds| {
+            b.iter(|| naive(bounds))
+        });
+        group.bench_with_input(BenchmarkId::new("grid", count), &bounds, |b, bounds| {
+            b.iter(|| grid(bounds))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, benchmark);
+criterion_main!(benches);
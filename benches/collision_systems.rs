@@ -0,0 +1,108 @@
+use asteroids::{
+    asteroid::{self, Asteroid},
+    collision::{
+        self,
+        cache::{Cache, ContactEvent},
+        damages::DamageEvent,
+        generic::CollisionEvent,
+    },
+    component::{AngularVelocity, Health, Velocity},
+    plugin::{CLEANUP, FIXED_UPDATE, PRE_UPDATE, UPDATE},
+    tick::{self, Lockstep},
+    TIMESTEP,
+};
+use bevy::{prelude::*, utils::Duration};
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use iyes_loopless::prelude::*;
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg32;
+
+const COUNTS: [usize; 3] = [50, 100, 200];
+const TICKS: usize = 10;
+
+// Asteroids crowded in a square of side 2000, drifting in random directions,
+// with the systems computing their masses, moving them and resolving their collisions
+fn crowd(count: usize) -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(bevy::asset::AssetPlugin::default())
+        .add_plugin(bevy::transform::TransformPlugin)
+        .add_plugin(bevy::hierarchy::HierarchyPlugin)
+        .add_asset::<Mesh>()
+        .add_asset::<ColorMaterial>()
+        .insert_resource(Lockstep(Duration::from_secs_f32(TIMESTEP)))
+        .init_resource::<Cache>()
+        .add_event::<ContactEvent>()
+        .add_event::<CollisionEvent>()
+        .add_event::<DamageEvent>()
+        .add_fixed_timestep(Duration::from_secs_f32(TIMESTEP), FIXED_UPDATE)
+        .add_fixed_timestep_child_stage(FIXED_UPDATE)
+        .add_fixed_timestep_child_stage(FIXED_UPDATE)
+        .add_fixed_timestep_system(FIXED_UPDATE, PRE_UPDATE, collision::mass::update)
+        .add_fixed_timestep_system(FIXED_UPDATE, UPDATE, asteroid::movement.label("movement"))
+        .add_fixed_timestep_system(
+            FIXED_UPDATE,
+            UPDATE,
+            collision::generic::with::<Asteroid>.after("movement"),
+        )
+        .add_fixed_timestep_system(FIXED_UPDATE, CLEANUP, collision::cache::update)
+        .add_system_to_stage(CoreStage::Last, tick::lockstep)
+        .add_startup_system(
+            move |mut commands: Commands,
+                  mut materials: ResMut<Assets<ColorMaterial>>,
+                  mut meshes: ResMut<Assets<Mesh>>| {
+                let mut rng = Pcg32::seed_from_u64(count as u64);
+                for _ in 0..count {
+                    let radius = rng.gen_range(20.0..60.0);
+                    let translation = Vec3::new(
+                        rng.gen_range(-1000.0..1000.0),
+                        rng.gen_range(-1000.0..1000.0),
+                        0.0,
+                    );
+                    let velocity = Vec3::new(
+                        rng.gen_range(-300.0..300.0),
+                        rng.gen_range(-300.0..300.0),
+                        0.0,
+                    );
+                    asteroid::spawn_one(
+                        &mut commands,
+                        &mut materials,
+                        &mut meshes,
+                        asteroid::outline(&mut rng, radius),
+                        Health(100),
+                        Transform::from_translation(translation),
+                        Velocity(velocity),
+                        AngularVelocity(rng.gen_range(-2.0..2.0)),
+                    );
+                }
+            },
+        );
+
+    // Spawns the asteroids and computes their masses
+    app.update();
+    app.update();
+
+    app
+}
+
+fn benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("collision_systems");
+    for count in COUNTS {
+        group.bench_with_input(BenchmarkId::new("with", count), &count, |b, count| {
+            b.iter_batched(
+                || crowd(*count),
+                |mut app| {
+                    for _ in 0..TICKS {
+                        app.update();
+                    }
+                    app
+                },
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, benchmark);
+criterion_main!(benches);
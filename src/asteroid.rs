@@ -1,105 +1,272 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, render::mesh::PrimitiveTopology, sprite::Mesh2dHandle};
+use iyes_loopless::prelude::*;
 use rand::Rng;
 use std::f32::consts::PI;
 
 use crate::{
-    collision::detection::{Aabb, Collider, Topology},
-    component::{AngularVelocity, Health, Mass, MomentOfInertia, Part, Velocity},
-    constant::{WINDOW_WIDTH, WINDOW_Z},
+    collision::{
+        detection::{Aabb, Collider, Topology},
+        mass::MassProperties,
+    },
+    component::{AngularVelocity, Health, Mass, MomentOfInertia, Part, PhysicsMaterial, Velocity},
+    constant::{WINDOW_HEIGHT, WINDOW_WIDTH, WINDOW_Z},
+    map::{Sector, SectorKind},
+    rng::{GameRng, Stream},
+    tick,
 };
 
+const VERTICES: usize = 16;
+// Largest variation of the distance of the outline to the center, relative to the radius
+const ROUGHNESS: f32 = 0.4;
+
 #[derive(Clone, Component, Copy)]
 pub struct Asteroid;
 
+// Lumpy outline of an asteroid, in CCW order.
+//
+// Vertices are spread around the center at distances drawn around the radius,
+// each one averaged with its neighbours to smooth the outline.
+// The outline is star-shaped with respect to the center so it never crosses itself
+// and fanning triangles out of the center covers it.
+pub fn outline(rng: &mut impl Rng, radius: f32) -> Vec<Vec2> {
+    let noise: Vec<f32> = (0..VERTICES)
+        .map(|_| rng.gen_range(-ROUGHNESS..ROUGHNESS))
+        .collect();
+    let step = 2.0 * PI / VERTICES as f32;
+
+    (0..VERTICES)
+        .map(|i| {
+            let smoothed =
+                (noise[(i + VERTICES - 1) % VERTICES] + 2.0 * noise[i] + noise[(i + 1) % VERTICES])
+                    / 4.0;
+            let theta = (i as f32 + rng.gen_range(-0.25..0.25)) * step;
+            radius * (1.0 + smoothed) * Vec2::new(theta.cos(), theta.sin())
+        })
+        .collect()
+}
+
+// Asteroids appear in the sectors around the camera, at the rate and in the manner of their kind,
+// and only out of sight.
 pub fn spawn(
     mut commands: Commands,
     mut materials: ResMut<Assets<ColorMaterial>>,
     mut meshes: ResMut<Assets<Mesh>>,
+    mut stream: Local<Stream>,
+    game_rng: Res<GameRng>,
     query_camera: Query<&Transform, With<Camera>>,
+    query_sector: Query<(&Sector, &Transform, &Visibility), Without<Camera>>,
+    fixed_timesteps: Res<FixedTimesteps>,
 ) {
-    let mut rng = rand::thread_rng();
-    let Vec3 { x: xc, y: yc, z: _ } = query_camera.single().translation;
-    if rng.gen_range(0..10) == 0 {
-        let phi = rng.gen_range(0.0..2.0 * PI);
+    let rng = stream.get(&game_rng, "asteroid::spawn");
+    let delta = tick::delta(&fixed_timesteps);
+    let camera = query_camera.single().translation;
+    for (sector, transform, visibility) in &query_sector {
+        if !visibility.is_visible || rng.gen_range(0.0..1.0) >= sector.kind.density() * delta {
+            continue;
+        }
+
         let translation = Vec3::new(
-            xc + 2.0 * WINDOW_WIDTH * phi.cos(),
-            yc + 2.0 * WINDOW_WIDTH * phi.sin(),
+            transform.translation.x + rng.gen_range(-WINDOW_WIDTH / 2.0..WINDOW_WIDTH / 2.0),
+            transform.translation.y + rng.gen_range(-WINDOW_HEIGHT / 2.0..WINDOW_HEIGHT / 2.0),
             WINDOW_Z,
         );
-        const HEALTH_MAX: u32 = 60;
-        let health = Health(rng.gen_range(10..HEALTH_MAX + 1));
+        const MARGIN: f32 = 200.0;
+        if (translation.x - camera.x).abs() < WINDOW_WIDTH / 2.0 + MARGIN
+            && (translation.y - camera.y).abs() < WINDOW_HEIGHT / 2.0 + MARGIN
+        {
+            continue;
+        }
+
+        let (health_max, velocity_min, velocity_max, theta) = match sector.kind {
+            SectorKind::Belt => (30, 200.0, 300.0, sector.heading + rng.gen_range(-0.2..0.2)),
+            _ => (60, 100.0, 500.0, rng.gen_range(0.0..2.0 * PI)),
+        };
+        let health = Health(rng.gen_range(10..health_max + 1));
         let radius = (health.0 * 2) as f32;
-        let area = PI * radius.powi(2);
-        let mass = Mass(area);
-        let moment_of_inertia = MomentOfInertia(0.5 * mass.0 * radius.powi(2));
-        const VELOCITY_MIN: f32 = 100.0;
-        const VELOCITY_MAX: f32 = 500.0;
-        let rho = rng.gen_range(VELOCITY_MIN..VELOCITY_MAX);
-        let theta = rng.gen_range(0.0..2.0 * PI);
+        let rho = rng.gen_range(velocity_min..velocity_max);
         let velocity = Velocity(Vec3::new(rho * theta.cos(), rho * theta.sin(), 0.0));
-        let angular_velocity = AngularVelocity(0.0);
-
-        let asteroid = commands
-            .spawn(Asteroid)
-            .insert(mass)
-            .insert(moment_of_inertia)
-            .insert(velocity)
-            .insert(angular_velocity)
-            .insert(SpatialBundle {
-                transform: Transform::from_translation(translation),
-                ..Default::default()
-            })
-            .id();
-
-        const VERTICES: usize = 16;
-        const COLOR: Color = Color::rgb(0.25, 0.25, 0.25);
-
-        let asteroid_part = commands
-            .spawn((Asteroid, Part))
-            .insert(health)
-            .insert(Collider {
-                aabb: Aabb {
-                    hw: radius,
-                    hh: radius,
-                },
-                topology: Topology::Disk { radius },
-            })
-            .insert(ColorMesh2dBundle {
-                mesh: meshes
-                    .add(Mesh::from(shape::Circle {
-                        radius,
-                        vertices: VERTICES,
-                    }))
-                    .into(),
-                material: materials.add(COLOR.into()),
-                ..Default::default()
-            })
-            .id();
-
-        commands.entity(asteroid).add_child(asteroid_part);
+        const ANGULAR_VELOCITY_MAX: f32 = 2.0;
+        let angular_velocity =
+            AngularVelocity(rng.gen_range(-ANGULAR_VELOCITY_MAX..ANGULAR_VELOCITY_MAX));
+
+        spawn_one(
+            &mut commands,
+            &mut materials,
+            &mut meshes,
+            outline(rng, radius),
+            health,
+            Transform::from_translation(translation),
+            velocity,
+            angular_velocity,
+        );
     }
 }
 
-pub fn movement(
+// Outline of the part of an asteroid, as it was drawn
+pub fn outline_of(collider: &Collider, meshes: &Assets<Mesh>) -> Vec<Vec2> {
+    collider
+        .topology
+        .triangles(meshes)
+        .into_iter()
+        .map(|triangle| triangle.1)
+        .collect()
+}
+
+pub fn spawn_one(
+    commands: &mut Commands,
+    materials: &mut Assets<ColorMaterial>,
+    meshes: &mut Assets<Mesh>,
+    outline: Vec<Vec2>,
+    health: Health,
+    transform: Transform,
+    velocity: Velocity,
+    angular_velocity: AngularVelocity,
+) -> Entity {
+    let asteroid = commands
+        .spawn(Asteroid)
+        .insert(velocity)
+        .insert(angular_velocity)
+        .insert(SpatialBundle {
+            transform,
+            ..Default::default()
+        })
+        .id();
+
+    const COLOR: Color = Color::rgb(0.25, 0.25, 0.25);
+    let extent = outline
+        .iter()
+        .map(|vertex| vertex.length())
+        .fold(0.0, f32::max);
+    let positions: Vec<[f32; 3]> = outline
+        .iter()
+        .zip(outline.iter().cycle().skip(1))
+        .flat_map(|(a, b)| [Vec2::ZERO, *a, *b])
+        .map(|vertex| vertex.extend(0.0).to_array())
+        .collect();
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    let mesh_handle = meshes.add(mesh);
+
+    let asteroid_part = commands
+        .spawn((Asteroid, Part))
+        .insert(health)
+        .insert(PhysicsMaterial::ROCK)
+        .insert(Collider {
+            aabb: Aabb {
+                hw: extent,
+                hh: extent,
+            },
+            topology: Topology::Triangles {
+                mesh_handle: Mesh2dHandle(mesh_handle.clone_weak()),
+            },
+        })
+        .insert(ColorMesh2dBundle {
+            mesh: mesh_handle.into(),
+            material: materials.add(COLOR.into()),
+            ..Default::default()
+        })
+        .id();
+
+    commands.entity(asteroid).add_child(asteroid_part);
+
+    asteroid
+}
+
+// Asteroids break into fragments of equal size, as long as these are not too small
+#[derive(Clone, Copy, Debug, Resource)]
+pub struct Fragmentation {
+    pub fragments: usize,
+    pub radius_min: f32,
+}
+
+impl Default for Fragmentation {
+    fn default() -> Self {
+        Fragmentation {
+            fragments: 3,
+            radius_min: 20.0,
+        }
+    }
+}
+
+// Splits destroyed asteroids before they are wrecked.
+//
+// Fragments share the area, hence the mass, of their asteroid
+// and are spread evenly around its center, close to but not touching each other.
+// Each one moves along with the point of the asteroid it starts from, plus a kick outwards:
+// the kicks cancel out and so does the momentum due to the rotation, which conserves momentum.
+// Fragments spin so as to conserve angular momentum as well.
+pub fn fragment(
     mut commands: Commands,
-    mut query_asteroid: Query<
-        (&AngularVelocity, Entity, &mut Transform, &Velocity),
-        With<Asteroid>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut stream: Local<Stream>,
+    fragmentation: Res<Fragmentation>,
+    game_rng: Res<GameRng>,
+    query: Query<
+        (
+            &AngularVelocity,
+            &Mass,
+            &MomentOfInertia,
+            &Transform,
+            &Velocity,
+        ),
+        (With<Asteroid>, Without<Part>),
     >,
-    query_camera: Query<&Transform, (With<Camera>, Without<Asteroid>)>,
-    time: Res<Time>,
+    query_part: Query<(&Collider, &Health, &Parent), (With<Asteroid>, With<Part>)>,
 ) {
-    for (a_angular_velocity, a_id, mut a_transform, a_velocity) in query_asteroid.iter_mut() {
-        if (query_camera.single().translation - a_transform.translation)
-            .truncate()
-            .length()
-            > 2.5 * WINDOW_WIDTH
-        {
-            commands.entity(a_id).despawn_recursive();
-        } else {
-            a_transform.translation += a_velocity.0 * time.delta_seconds();
-            a_transform.rotation *=
-                Quat::from_axis_angle(Vec3::Z, a_angular_velocity.0 * time.delta_seconds());
+    let rng = stream.get(&game_rng, "asteroid::fragment");
+    for (collider, health, parent) in &query_part {
+        if health.0 > 0 || fragmentation.fragments < 2 {
+            continue;
+        }
+        let area = MassProperties::of_collider(collider, 1.0, &meshes).mass;
+        let n = fragmentation.fragments;
+        let fragment_radius = (area / (n as f32 * PI)).sqrt();
+        if fragment_radius < fragmentation.radius_min {
+            continue;
         }
+
+        let (angular_velocity, mass, moment_of_inertia, transform, velocity) =
+            query.get(**parent).unwrap();
+        commands.entity(**parent).despawn_recursive();
+
+        const KICK: f32 = 50.0;
+        const GAP: f32 = 2.0;
+        let distance = (fragment_radius + GAP) / (PI / n as f32).sin();
+        // Fragments are taken as disks for their moment of inertia
+        let fragment_angular_velocity = AngularVelocity(
+            angular_velocity.0 * (moment_of_inertia.0 - mass.0 * distance.powi(2))
+                / (0.5 * mass.0 * fragment_radius.powi(2)),
+        );
+        let phase = rng.gen_range(0.0..2.0 * PI);
+        for i in 0..n {
+            let theta = phase + i as f32 * 2.0 * PI / n as f32;
+            let direction = Vec3::new(theta.cos(), theta.sin(), 0.0);
+            let offset = distance * direction;
+
+            spawn_one(
+                &mut commands,
+                &mut materials,
+                &mut meshes,
+                outline(rng, fragment_radius),
+                Health((fragment_radius / 2.0).ceil() as u32),
+                Transform::from_translation(transform.translation + offset),
+                Velocity(
+                    velocity.0 + angular_velocity.0 * Vec3::Z.cross(offset) + KICK * direction,
+                ),
+                fragment_angular_velocity,
+            );
+        }
+    }
+}
+
+pub fn movement(
+    mut query_asteroid: Query<(&AngularVelocity, &mut Transform, &Velocity), With<Asteroid>>,
+    fixed_timesteps: Res<FixedTimesteps>,
+) {
+    let delta = tick::delta(&fixed_timesteps);
+    for (a_angular_velocity, mut a_transform, a_velocity) in query_asteroid.iter_mut() {
+        a_transform.translation += a_velocity.0 * delta;
+        a_transform.rotation *= Quat::from_axis_angle(Vec3::Z, a_angular_velocity.0 * delta);
     }
 }
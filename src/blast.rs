@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 
-use crate::component::Health;
+use crate::{component::Health, shared_assets::SharedAssets};
 
 #[derive(Component)]
 pub struct Blast;
@@ -17,20 +17,18 @@ pub fn spawn(
     mut commands: Commands,
     mut materials: ResMut<Assets<ColorMaterial>>,
     mut meshes: ResMut<Assets<Mesh>>,
+    mut shared_assets: ResMut<SharedAssets>,
 ) {
     for ev in blast_event.iter() {
         commands
             .spawn(Blast)
             .insert(Health(1))
             .insert(ColorMesh2dBundle {
-                mesh: meshes
-                    .add(Mesh::from(shape::Circle {
-                        radius: ev.radius,
-                        vertices: ev.vertices,
-                    }))
+                mesh: shared_assets
+                    .circle(&mut meshes, ev.radius, ev.vertices)
                     .into(),
                 transform: Transform::from_translation(ev.translation + Vec3::new(0.0, 0.0, 1.0)),
-                material: materials.add(ev.color.into()),
+                material: shared_assets.material(&mut materials, ev.color),
                 ..Default::default()
             });
     }
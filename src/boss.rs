@@ -1,4 +1,5 @@
 use bevy::{prelude::*, render::mesh::PrimitiveTopology, sprite::Mesh2dHandle};
+use iyes_loopless::prelude::*;
 use rand::Rng;
 use std::f32::consts::{PI, SQRT_2};
 
@@ -6,12 +7,16 @@ use crate::{
     blast::BlastEvent,
     collision::detection::{triangle::Triangle, Aabb, Collider, Topology},
     component::{
-        AngularVelocity, Attack, ColorDamaged, Health, Indestructible, Mass, MomentOfInertia, Part,
-        Velocity,
+        AngularVelocity, Attack, ColorDamaged, Health, Indestructible, Part, PhysicsMaterial,
+        Resistances, Velocity,
     },
-    constant::WINDOW_Z,
+    constant::{ANGULAR_DRAG, DRAG, WINDOW_Z},
+    explosion::Explosive,
     fire::{Fire, FireEvent},
+    rng::{GameRng, Stream},
     spaceship::{self, Spaceship},
+    tick,
+    waypoint::{Waypoint, WaypointKind},
 };
 
 const INNER_RADIUS: f32 = 100.0;
@@ -104,23 +109,18 @@ pub fn spawn(
     mut commands: Commands,
     mut materials: ResMut<Assets<ColorMaterial>>,
     mut meshes: ResMut<Assets<Mesh>>,
+    game_rng: Res<GameRng>,
 ) {
-    let mut rng = rand::thread_rng();
+    let mut rng = game_rng.fork("boss::spawn");
     let theta = rng.gen_range(0.0..2.0 * PI);
     const DISTANCE_TO_BOSS: f32 = 10000.0;
     let x = DISTANCE_TO_BOSS * theta.cos() + spaceship::TRANSLATION.x;
     let y = DISTANCE_TO_BOSS * theta.sin() + spaceship::TRANSLATION.y;
     let translation = Vec3::new(x, y, WINDOW_Z);
-    const AREA: f32 =
-        PI * (INNER_RADIUS + OUTER_RADIUS) / 2.0 * (INNER_RADIUS + OUTER_RADIUS) / 2.0;
-    const MASS: f32 = AREA;
-    const MOMENT_OF_INERTIA: f32 =
-        0.5 * MASS * (INNER_RADIUS + OUTER_RADIUS) / 2.0 * (INNER_RADIUS + OUTER_RADIUS) / 2.0;
 
     let boss = commands
         .spawn(Boss)
-        .insert(Mass(MASS))
-        .insert(MomentOfInertia(MOMENT_OF_INERTIA))
+        .insert(Waypoint::new(WaypointKind::Objective, "Target"))
         .insert(Velocity(Vec3::ZERO))
         .insert(AngularVelocity(0.0))
         .insert(SpatialBundle {
@@ -149,6 +149,8 @@ pub fn spawn(
         .insert(BossCore)
         .insert(Health(CORE_HEALTH))
         .insert(Indestructible)
+        // Everything bounces off the core
+        .insert(PhysicsMaterial::SHIELD)
         .insert(Collider {
             aabb: Aabb {
                 hw: 108.3, // sqrt(100^2 + (100sqrt(2) - 100)^2)
@@ -190,6 +192,17 @@ pub fn spawn(
             .insert(BossEdge)
             .insert(Health(EDGE_HEALTH))
             .insert(ColorDamaged(Color::GRAY))
+            .insert(PhysicsMaterial::HULL)
+            // Ramming the boss does little harm, it has to be shot at
+            .insert(Resistances {
+                kinetic: 0.5,
+                ..default()
+            })
+            .insert(Explosive {
+                radius: 250.0,
+                damages: 10.0,
+                impulse: 1000000.0,
+            })
             .insert(Collider {
                 aabb: Aabb {
                     hw: OUTER_RADIUS - INNER_RADIUS,
@@ -223,9 +236,13 @@ pub fn spawn(
 pub fn movement(
     mut query_boss: Query<(&mut AngularVelocity, &mut Transform, &mut Velocity), With<Boss>>,
     query_boss_edge: Query<With<BossEdge>>,
+    mut stream: Local<Stream>,
+    game_rng: Res<GameRng>,
     query_spaceship: Query<&Transform, (With<Spaceship>, Without<Part>, Without<Boss>)>,
-    time: Res<Time>,
+    fixed_timesteps: Res<FixedTimesteps>,
 ) {
+    let delta = tick::delta(&fixed_timesteps);
+    let rng = stream.get(&game_rng, "boss::movement");
     if let Ok((mut angular_velocity, mut b_transform, mut velocity)) = query_boss.get_single_mut() {
         const ACCELERATION: f32 = 500.0;
         const ROTATION_SPEED: f32 = 20.0;
@@ -233,42 +250,40 @@ pub fn movement(
         if let Ok(s_transform) = query_spaceship.get_single() {
             if !query_boss_edge.is_empty() {
                 let mut direction = (s_transform.translation - b_transform.translation).normalize();
-                let mut rng = rand::thread_rng();
                 let angle = rng.gen_range(-PI / 2.0..PI / 2.0);
                 direction = Quat::from_axis_angle(Vec3::Z, angle) * direction;
-                velocity.0 += ACCELERATION * time.delta_seconds() * direction;
-                angular_velocity.0 += ROTATION_SPEED * time.delta_seconds();
+                velocity.0 += ACCELERATION * delta * direction;
+                angular_velocity.0 += ROTATION_SPEED * delta;
             } else {
                 let direction = (s_transform.translation - b_transform.translation).normalize();
-                velocity.0 += 2.0 * ACCELERATION * time.delta_seconds() * direction;
-                angular_velocity.0 += 2.0 * ROTATION_SPEED * time.delta_seconds();
+                velocity.0 += 2.0 * ACCELERATION * delta * direction;
+                angular_velocity.0 += 2.0 * ROTATION_SPEED * delta;
             }
         } else {
-            angular_velocity.0 -= ROTATION_SPEED * time.delta_seconds();
+            angular_velocity.0 -= ROTATION_SPEED * delta;
         }
 
-        const DRAG: f32 = 0.05;
-        velocity.0 *= 1.0 - DRAG;
-        const ANGULAR_DRAG: f32 = 0.25;
-        angular_velocity.0 *= 1.0 - ANGULAR_DRAG;
+        velocity.0 *= (-DRAG * delta).exp();
+        angular_velocity.0 *= (-ANGULAR_DRAG * delta).exp();
 
-        b_transform.translation += velocity.0 * time.delta_seconds();
-        b_transform.rotation *=
-            Quat::from_axis_angle(Vec3::Z, angular_velocity.0 * time.delta_seconds());
+        b_transform.translation += velocity.0 * delta;
+        b_transform.rotation *= Quat::from_axis_angle(Vec3::Z, angular_velocity.0 * delta);
     }
 }
 
 pub fn attack(
     mut blast_event: EventWriter<BlastEvent>,
     mut fire_event: EventWriter<FireEvent>,
+    mut stream: Local<Stream>,
+    game_rng: Res<GameRng>,
     query_boss: Query<&Transform, (With<Boss>, Without<Part>)>,
     query_boss_edge: Query<(&Attack, &Transform), With<Boss>>,
     query_spaceship: Query<&Transform, (With<Spaceship>, Without<Part>)>,
 ) {
+    let rng = stream.get(&game_rng, "boss::attack");
     if let Ok(b_transform) = query_boss.get_single() {
         if let Ok(s_transform) = query_spaceship.get_single() {
             for (bp_attack, bp_transform) in query_boss_edge.iter() {
-                let mut rng = rand::thread_rng();
                 const ATTACK_RATE: usize = 10;
                 if rng.gen_range(0..ATTACK_RATE) == 0 {
                     let attack_absolute_translation =
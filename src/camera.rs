@@ -1,11 +1,12 @@
 use bevy::prelude::*;
-// use iyes_loopless::prelude::NextState;
+use iyes_loopless::prelude::*;
 
 use crate::{
     component::Velocity,
     constant::{WINDOW_HEIGHT, WINDOW_WIDTH, WINDOW_Z},
-    keyboard_bindings::KeyboardBindings,
+    intent::Intent,
     spaceship::Spaceship,
+    tick,
 };
 
 const CAMERA_Z: f32 = 1000.0;
@@ -43,17 +44,17 @@ pub fn setup(mut query: Query<(&mut Camera, &mut CameraPositioning, &mut Transfo
 
 pub fn update(
     mut query_camera: Query<(&mut CameraPositioning, &mut Transform), With<Camera>>,
-    keys: Res<Input<KeyCode>>,
-    query_bindings: Query<&KeyboardBindings>,
+    intent: Res<Intent>,
     query_spaceship: Query<(&Transform, &Velocity), (With<Spaceship>, Without<Camera>)>,
-    time: Res<Time>,
+    fixed_timesteps: Res<FixedTimesteps>,
 ) {
+    let delta = tick::delta(&fixed_timesteps);
     if let Ok((s_transform, s_velocity)) = query_spaceship.get_single() {
         let (mut c_positioning, mut c_transform) = query_camera.single_mut();
 
-        c_transform.translation += s_velocity.0 * time.delta_seconds();
+        c_transform.translation += s_velocity.0 * delta;
 
-        if keys.just_pressed(query_bindings.single().camera()) {
+        if intent.camera {
             *c_positioning = match *c_positioning {
                 CameraPositioning::Synchronized => CameraPositioning::Ahead,
                 CameraPositioning::Ahead => CameraPositioning::Synchronized,
@@ -93,14 +94,14 @@ pub fn update(
             }
             let c_destination = s_transform.translation + Vec3::new(x, y, CAMERA_Z - WINDOW_Z);
             let c_path = c_destination - c_transform.translation;
-            c_transform.translation += SPEED * time.delta_seconds() * c_path;
+            c_transform.translation += SPEED * delta * c_path;
         } else {
             let direction = Vec3 {
                 x: s_transform.translation.x - c_transform.translation.x,
                 y: s_transform.translation.y - c_transform.translation.y,
                 z: 0.0,
             };
-            c_transform.translation += SPEED * time.delta_seconds() * direction;
+            c_transform.translation += SPEED * delta * direction;
         }
     }
 }
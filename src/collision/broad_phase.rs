@@ -0,0 +1,139 @@
+use bevy::{prelude::*, utils::HashMap};
+
+use super::detection::Collider;
+
+// Side of the square cells of the grid.
+// Bodies overlapping several cells are registered in each of them.
+pub const CELL_SIZE: f32 = 256.0;
+
+// Axis-aligned box in world coordinates
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Bounds {
+    pub min: Vec2,
+    pub max: Vec2,
+}
+
+impl Bounds {
+    pub fn from_center(center: Vec2, half_extents: Vec2) -> Bounds {
+        Bounds {
+            min: center - half_extents,
+            max: center + half_extents,
+        }
+    }
+
+    pub fn union(&self, other: Bounds) -> Bounds {
+        Bounds {
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+        }
+    }
+
+    pub fn intersects(&self, other: Bounds) -> bool {
+        self.min.x <= other.max.x
+            && other.min.x <= self.max.x
+            && self.min.y <= other.max.y
+            && other.min.y <= self.max.y
+    }
+}
+
+// Bounds of a body over the last tick whatever its rotation, given its transform at the end of the tick
+// and the colliders and transforms of its parts.
+pub fn body_bounds<'a>(
+    transform: &Transform,
+    velocity: Vec3,
+    delta: f32,
+    parts: impl IntoIterator<Item = (&'a Collider, &'a Transform)>,
+) -> Bounds {
+    let reach = parts
+        .into_iter()
+        .map(|(collider, part_transform)| {
+            (transform.transform_point(part_transform.translation) - transform.translation)
+                .truncate()
+                .length()
+                + Vec2::new(collider.aabb.hw, collider.aabb.hh).length()
+        })
+        .fold(0.0, f32::max);
+    let end = transform.translation.truncate();
+    let start = end - velocity.truncate() * delta;
+
+    Bounds::from_center(end, Vec2::splat(reach))
+        .union(Bounds::from_center(start, Vec2::splat(reach)))
+}
+
+// Uniform grid of cells keyed by their coordinates (i, j), like map sectors.
+// Bodies are identified by their order of insertion.
+#[derive(Debug)]
+pub struct Grid {
+    cell_size: f32,
+    cells: HashMap<(isize, isize), Vec<usize>>,
+    bounds: Vec<Bounds>,
+}
+
+impl Grid {
+    pub fn new(cell_size: f32) -> Grid {
+        Grid {
+            cell_size,
+            cells: HashMap::default(),
+            bounds: Vec::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.bounds.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bounds.is_empty()
+    }
+
+    pub fn insert(&mut self, bounds: Bounds) -> usize {
+        let index = self.bounds.len();
+        self.bounds.push(bounds);
+        for cell in self.cells_of(bounds) {
+            self.cells.entry(cell).or_default().push(index);
+        }
+
+        index
+    }
+
+    fn cells_of(&self, bounds: Bounds) -> impl Iterator<Item = (isize, isize)> {
+        let [i_min, j_min] = (bounds.min / self.cell_size).floor().to_array();
+        let [i_max, j_max] = (bounds.max / self.cell_size).floor().to_array();
+        let (j_min, j_max) = (j_min as isize, j_max as isize);
+
+        (i_min as isize..=i_max as isize).flat_map(move |i| (j_min..=j_max).map(move |j| (i, j)))
+    }
+
+    // Bodies whose bounds intersect the given ones, in increasing order
+    pub fn query(&self, bounds: Bounds) -> Vec<usize> {
+        let mut indices: Vec<usize> = self
+            .cells_of(bounds)
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .copied()
+            .filter(|&index| self.bounds[index].intersects(bounds))
+            .collect();
+        indices.sort_unstable();
+        indices.dedup();
+
+        indices
+    }
+
+    // Pairs (i, j) with i < j of bodies whose bounds intersect, in lexicographic order
+    pub fn pairs(&self) -> Vec<(usize, usize)> {
+        let mut pairs = Vec::new();
+        for indices in self.cells.values() {
+            for (k, &i) in indices.iter().enumerate() {
+                for &j in &indices[k + 1..] {
+                    if self.bounds[i].intersects(self.bounds[j]) {
+                        pairs.push((i.min(j), i.max(j)));
+                    }
+                }
+            }
+        }
+        pairs.sort_unstable();
+        pairs.dedup();
+
+        pairs
+    }
+}
@@ -1,31 +1,61 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, utils::StableHashSet};
+use std::hash::{Hash, Hasher};
 
+// Pair of bodies in contact, in any order
 #[derive(Clone, Copy, Debug, Eq)]
 pub struct Collision(pub Entity, pub Entity);
 
 impl PartialEq for Collision {
     fn eq(&self, other: &Self) -> bool {
-        (self.0 == other.0 && self.1 == other.1) || (self.0 == other.1 && self.1 == other.0)
+        self.key() == other.key()
     }
 }
 
+impl Hash for Collision {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+impl Collision {
+    // The bodies ordered by entity, the same whichever order the pair was given in
+    fn key(&self) -> (Entity, Entity) {
+        if self.0 <= self.1 {
+            (self.0, self.1)
+        } else {
+            (self.1, self.0)
+        }
+    }
+
+    pub fn involves(&self, e: Entity) -> bool {
+        e == self.0 || e == self.1
+    }
+}
+
+// Collisions resolved during the last tick (old) and the current one (new).
+// The sets have a fixed hasher so that the same contacts are always visited in the same order.
 #[derive(Debug, Default, Resource)]
 pub struct Cache {
-    pub old: Vec<Collision>,
-    pub new: Vec<Collision>,
+    pub old: StableHashSet<Collision>,
+    pub new: StableHashSet<Collision>,
 }
 
 impl Cache {
     pub fn add(&mut self, collision: Collision) {
-        self.new.push(collision);
+        self.new.insert(collision);
     }
 
     pub fn contains(&self, collision: Collision) -> bool {
         self.old.contains(&collision)
     }
 
+    // Whether the collision has already been resolved during the current tick
+    pub fn resolved(&self, collision: Collision) -> bool {
+        self.new.contains(&collision)
+    }
+
     pub fn contains_entity(&self, e: Entity) -> bool {
-        self.old.iter().any(|&Collision(a, b)| e == a || e == b)
+        self.old.iter().any(|collision| collision.involves(e))
     }
 
     pub fn update(&mut self) {
@@ -34,6 +64,49 @@ impl Cache {
     }
 }
 
-pub fn update(mut cache: ResMut<Cache>) {
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Phase {
+    // The bodies were not in contact during the previous tick
+    Begin,
+    // The bodies were already in contact during the previous tick.
+    // Such contacts still bounce the bodies but deal no damages, on purpose:
+    // bodies resting against each other would otherwise wear each other down tick after tick.
+    Persist,
+    // The bodies were in contact during the previous tick but no longer are
+    End,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ContactEvent {
+    pub collision: Collision,
+    pub phase: Phase,
+}
+
+// Reports the contacts of the tick then makes them the old ones.
+// Runs once all collisions of the tick have been resolved.
+pub fn update(mut cache: ResMut<Cache>, mut contact_event: EventWriter<ContactEvent>) {
+    // Events are sent by entity pair, independently of the layout of the sets
+    let mut new: Vec<_> = cache.new.iter().copied().collect();
+    new.sort_unstable_by_key(Collision::key);
+    let mut old: Vec<_> = cache.old.difference(&cache.new).copied().collect();
+    old.sort_unstable_by_key(Collision::key);
+
+    for collision in new {
+        contact_event.send(ContactEvent {
+            collision,
+            phase: if cache.contains(collision) {
+                Phase::Persist
+            } else {
+                Phase::Begin
+            },
+        });
+    }
+    for collision in old {
+        contact_event.send(ContactEvent {
+            collision,
+            phase: Phase::End,
+        });
+    }
+
     cache.update();
 }
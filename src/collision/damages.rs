@@ -1,53 +1,168 @@
 use bevy::prelude::*;
 
 use crate::{
-    component::{ColorDamaged, Health, Indestructible, Mass, Velocity},
+    component::{
+        Armor, ColorDamaged, CriticalZone, DamageKind, Health, Indestructible, Mass, Resistances,
+        Velocity,
+    },
     fire::Damages,
+    transform,
 };
 
+// Damages applied to a part.
+// The amount is the one before mitigation, the damages dealt are the health actually removed.
+#[derive(Clone, Copy, Debug)]
+pub struct DamageEvent {
+    pub part: Entity,
+    pub kind: DamageKind,
+    pub amount: f32,
+    pub dealt: u32,
+    pub critical: bool,
+    pub point: Vec2,
+}
+
+// Components of a part taking damages, as queried by the systems damaging it
+pub type DamagedPart<'a> = (
+    Entity,
+    &'a Handle<ColorMaterial>,
+    Option<&'a ColorDamaged>,
+    Mut<'a, Health>,
+    Option<&'a Indestructible>,
+    Option<&'a Armor>,
+    Option<&'a Resistances>,
+    Option<&'a CriticalZone>,
+    &'a Transform,
+);
+
+// Amount of damages left once the critical multiplier, the resistance and the armor are applied
+pub fn mitigate(
+    amount: f32,
+    kind: DamageKind,
+    multiplier: f32,
+    maybe_resistances: Option<&Resistances>,
+    maybe_armor: Option<&Armor>,
+) -> u32 {
+    let resistance = maybe_resistances.map_or(0.0, |resistances| resistances.to(kind));
+    let damages = (amount * multiplier * (1.0 - resistance.clamp(0.0, 1.0))) as u32;
+
+    damages.saturating_sub(maybe_armor.map_or(0, |armor| armor.0))
+}
+
+// Deals damages of the given kind to the part of a body at point, given in world coordinates.
+// Returns the health removed.
+pub fn deal(
+    (
+        part,
+        color_material,
+        maybe_color_damaged,
+        mut health,
+        maybe_indestructible,
+        maybe_armor,
+        maybe_resistances,
+        maybe_critical_zone,
+        part_transform,
+    ): DamagedPart,
+    body_transform: Transform,
+    amount: f32,
+    kind: DamageKind,
+    point: Vec2,
+    materials: &mut Assets<ColorMaterial>,
+    damage_event: &mut EventWriter<DamageEvent>,
+) -> u32 {
+    let global_transform = transform::global_of(*part_transform, body_transform);
+    let local_point = global_transform
+        .compute_affine()
+        .inverse()
+        .transform_point3(point.extend(0.0))
+        .truncate();
+    let maybe_critical_zone =
+        maybe_critical_zone.filter(|zone| local_point.distance(zone.center) <= zone.radius);
+    let multiplier = maybe_critical_zone.map_or(1.0, |zone| zone.multiplier);
+
+    let mut dealt = 0;
+    if maybe_indestructible.is_none() {
+        dealt = health.0.min(mitigate(
+            amount,
+            kind,
+            multiplier,
+            maybe_resistances,
+            maybe_armor,
+        ));
+        health.0 -= dealt;
+        if let Some(ColorDamaged(wreck_color)) = maybe_color_damaged {
+            let color = &mut materials.get_mut(color_material).unwrap().color;
+            if health.0 > 0 {
+                let [wr, wg, wb, _] = wreck_color.as_rgba_f32();
+                let [mut r, mut g, mut b, _] = color.as_rgba_f32();
+                r += (wr - r) / health.0 as f32;
+                g += (wg - g) / health.0 as f32;
+                b += (wb - b) / health.0 as f32;
+                *color = Color::rgb(r, g, b);
+            }
+        }
+    }
+
+    if amount > 0.0 {
+        damage_event.send(DamageEvent {
+            part,
+            kind,
+            amount,
+            dealt,
+            critical: maybe_critical_zone.is_some(),
+            point,
+        });
+    }
+
+    dealt
+}
+
+// Damages dealt to each other by two colliding bodies: fires deal their own damages
+// while other bodies deal kinetic damages growing with their mass and their speed along the normal.
 pub fn apply<'a, I>(
     parts: I,
+    transforms: [Transform; 2],
     damages1: Option<&Damages>,
     damages2: Option<&Damages>,
     mass1: Mass,
     mass2: Mass,
     velocity1: Velocity,
     velocity2: Velocity,
+    point: Vec2,
     normal: Vec2,
     materials: &mut Assets<ColorMaterial>,
-) where
-    I: IntoIterator<
-        Item = (
-            &'a Handle<ColorMaterial>,
-            Option<&'a ColorDamaged>,
-            Mut<'a, Health>,
-            Option<&'a Indestructible>,
-        ),
-    >,
+    damage_event: &mut EventWriter<DamageEvent>,
+) -> [u32; 2]
+where
+    I: IntoIterator<Item = DamagedPart<'a>>,
 {
     let dv = (velocity1.0 - velocity2.0).truncate().dot(normal).abs();
     let [damages1, damages2] = [
-        damages2.map_or_else(|| (mass2.0.sqrt() * dv) as u32 / 2000, |d| d.0),
-        damages1.map_or_else(|| (mass1.0.sqrt() * dv) as u32 / 2000, |d| d.0),
+        damages2.map_or_else(
+            || (mass2.0.sqrt() * dv / 2000.0, DamageKind::Kinetic),
+            |d| (d.amount as f32, d.kind),
+        ),
+        damages1.map_or_else(
+            || (mass1.0.sqrt() * dv / 2000.0, DamageKind::Kinetic),
+            |d| (d.amount as f32, d.kind),
+        ),
     ];
 
-    for ((color_material, maybe_color_damaged, mut health, maybe_indestructible), damages) in
-        parts.into_iter().zip([damages1, damages2])
+    let mut dealt = [0; 2];
+    for ((part, transform), ((amount, kind), dealt)) in parts
+        .into_iter()
+        .zip(transforms)
+        .zip([damages1, damages2].into_iter().zip(&mut dealt))
     {
-        if maybe_indestructible.is_some() {
-            continue;
-        }
-        health.0 = health.0.saturating_sub(damages);
-        if let Some(ColorDamaged(wreck_color)) = maybe_color_damaged {
-            let color = &mut materials.get_mut(color_material).unwrap().color;
-            if health.0 > 0 {
-                let [wr, wg, wb, _] = wreck_color.as_rgba_f32();
-                let [mut r, mut g, mut b, _] = color.as_rgba_f32();
-                r += (wr - r) / health.0 as f32;
-                g += (wg - g) / health.0 as f32;
-                b += (wb - b) / health.0 as f32;
-                *color = Color::rgb(r, g, b);
-            }
-        }
+        *dealt = deal(
+            part,
+            transform,
+            amount,
+            kind,
+            point,
+            materials,
+            damage_event,
+        );
     }
+
+    dealt
 }
@@ -1,12 +1,13 @@
-use bevy::{prelude::*, render::mesh::VertexAttributeValues, sprite::Mesh2dHandle};
-
-use crate::{
-    collision::response,
-    component::{AngularVelocity, Mass, MomentOfInertia, Velocity},
-    transform,
+use bevy::{
+    ecs::query::ReadOnlyWorldQuery, prelude::*, render::mesh::VertexAttributeValues,
+    sprite::Mesh2dHandle,
 };
+
+use crate::{component::Velocity, transform};
 use triangle::TriangleXY;
 
+pub mod polygon;
+pub mod sweep;
 pub mod triangle;
 
 #[derive(Clone, Component)]
@@ -26,12 +27,73 @@ pub enum Topology {
     Point,
     Disk { radius: f32 },
     Triangles { mesh_handle: Mesh2dHandle },
+    // Vertices in CCW order
+    ConvexPolygon { vertices: Vec<Vec2> },
+}
+
+impl Topology {
+    // Triangles covering the shape, empty unless it is made of triangles or is a polygon
+    pub fn triangles(&self, meshes: &Assets<Mesh>) -> Vec<TriangleXY> {
+        match self {
+            Topology::ConvexPolygon { vertices } => polygon::triangles(vertices),
+            Topology::Triangles { mesh_handle } => {
+                if let Some(VertexAttributeValues::Float32x3(vertices)) = meshes
+                    .get(&mesh_handle.0)
+                    .unwrap()
+                    .attribute(Mesh::ATTRIBUTE_POSITION)
+                {
+                    vertices
+                        .chunks_exact(3)
+                        .map(|triplet| <[_; 3]>::try_from(triplet).expect("3 items").into())
+                        .collect()
+                } else {
+                    panic!("Cannot access triangle's mesh");
+                }
+            }
+            Topology::Point | Topology::Disk { .. } => Vec::new(),
+        }
+    }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Contact {
+    // Deepest point of contact
     pub point: Vec2,
+    // Second point of contact of shapes touching along an edge
+    pub point2: Option<Vec2>,
     pub normal: Vec2,
+    // Penetration along the normal
+    pub depth: f32,
+}
+
+impl Contact {
+    pub fn new(point: Vec2, normal: Vec2, depth: f32) -> Contact {
+        Contact {
+            point,
+            point2: None,
+            normal,
+            depth,
+        }
+    }
+
+    // Point of application of the impulse, between both points of contact if there are two
+    pub fn center(&self) -> Vec2 {
+        self.point2
+            .map_or(self.point, |point2| (self.point + point2) / 2.0)
+    }
+
+    fn transformed(&self, transform: Transform) -> Contact {
+        let transform_point = |point: Vec2| transform.transform_point(point.extend(0.0)).truncate();
+        Contact {
+            point: transform_point(self.point),
+            point2: self.point2.map(transform_point),
+            normal: transform
+                .rotation
+                .mul_vec3(self.normal.extend(0.0))
+                .truncate(),
+            depth: self.depth,
+        }
+    }
 }
 
 // Determines if point p is in the rectangle of center c, half width hw and half height hh
@@ -39,24 +101,28 @@ pub fn point_in_rectangle(p: Vec2, c: Vec2, hw: f32, hh: f32) -> bool {
     p.x >= c.x - hw && p.x <= c.x + hw && p.y >= c.y - hh && p.y <= c.y + hh
 }
 
-// Determines if point p is in CCW triangle (abc).
+// Determines if point p is in triangle (abc), whichever way it is wound.
 //
 // p lies in (abc) iff for all three lines (ab), (bc) and (ca),
 // p lies on the same side (either left or right) of each line.
 // Equivalently, p lies in (abc) iff (det(pa, pb) >= 0, det(pb, pc) >= 0 and det(pc, pa) >= 0) or
 // (det(pa, pb) <= 0, det(pb, pc) <= 0 and det(pc, pa) <= 0).
 //
-// Since (abc) is CCW, this is equivalent to
+// Once (abc) is made CCW, this is equivalent to
 // det(pa, pb) >= 0, det(pb, pc) >= 0 and det(pc, pa) >= 0
+//
+// Besides, det(pa, pb) / |ab| is the distance from p to line (ab).
+// The contact normal points into the triangle through its side closest to p.
 pub fn point_in_triangle(p: Vec2, t: impl Into<TriangleXY>) -> Option<Contact> {
-    let [a, b, c] = t.into().to_array();
+    let [a, b, c] = polygon::ccw(t);
     let [pa, pb, pc] = [a - p, b - p, c - p];
+    let [dab, dbc, dca] = [pa.perp_dot(pb), pb.perp_dot(pc), pc.perp_dot(pa)];
 
-    if pa.perp_dot(pb) > 0.0 && pb.perp_dot(pc) > 0.0 && pc.perp_dot(pa) > 0.0 {
-        Some(Contact {
-            point: p,
-            normal: ((a + b + c) / 3.0 - p).normalize(),
-        })
+    if dab > 0.0 && dbc > 0.0 && dca > 0.0 {
+        [(dab, b - a), (dbc, c - b), (dca, a - c)]
+            .into_iter()
+            .map(|(det, side)| Contact::new(p, side.perp().normalize(), det / side.length()))
+            .min_by(|contact1, contact2| contact1.depth.total_cmp(&contact2.depth))
     } else {
         None
     }
@@ -115,10 +181,12 @@ pub fn disk_intersects_line_segment(c: Vec2, r: f32, a: Vec2, b: Vec2) -> Option
     let mc = c - m;
     if mc.length() <= r {
         // println!("b");
-        Some(Contact {
-            point: m,
-            normal: mc.normalize(),
-        })
+        // A center lying on the segment is pushed out along x
+        Some(Contact::new(
+            m,
+            mc.try_normalize().unwrap_or(Vec2::X),
+            r - mc.length(),
+        ))
     } else {
         None
     }
@@ -173,32 +241,21 @@ pub fn circle_intersects_line_segment(o: Vec2, r: f32, m: Vec2, n: Vec2) -> bool
     }
 }
 
-// Determines if the disk of center o and radius r intersects the CCW triangle abc
+// Determines if the disk of center o and radius r intersects the triangle abc,
+// whichever way it is wound, including when o lies inside the triangle
 pub fn disk_intersects_triangle(o: Vec2, r: f32, t: impl Into<TriangleXY>) -> Option<Contact> {
-    let [a, b, c] = t.into().to_array();
-    // a.distance(o) < r
-    disk_intersects_line_segment(o, r, a, b)
-        .or_else(|| disk_intersects_line_segment(o, r, b, c))
-        .or_else(|| disk_intersects_line_segment(o, r, c, a))
-    // || point_in_triangle(o, [a, b, c])
+    polygon::disk_intersects_polygon(o, r, &polygon::ccw(t))
 }
 
+// Determines if triangles t1 and t2 intersect, including when one contains the other
+// (a triangle lying inside the other used to be reported as not intersecting it).
+// The normal points towards t1.
 pub fn triangles_intersect(
     t1: impl Into<TriangleXY>,
     t2: impl Into<TriangleXY>,
 ) -> Option<Contact> {
-    let [a1, b1, c1] = t1.into().to_array();
-    let [a2, b2, c2] = t2.into().to_array();
-
-    // We only need to test 8 line segments intersections.
-    line_segments_intersect(a1, b1 - a1, a2, b2 - a2)
-        .or_else(|| line_segments_intersect(a1, b1 - a1, b2, c2 - b2))
-        .or_else(|| line_segments_intersect(a1, b1 - a1, c2, a2 - c2))
-        .or_else(|| line_segments_intersect(b1, c1 - b1, a2, b2 - a2))
-        .or_else(|| line_segments_intersect(b1, c1 - b1, b2, c2 - b2))
-        .or_else(|| line_segments_intersect(b1, c1 - b1, c2, a2 - c2))
-        .or_else(|| line_segments_intersect(c1, a1 - c1, a2, b2 - a2))
-        .or_else(|| line_segments_intersect(c1, a1 - c1, b2, c2 - b2))
+    // Separating axes are computed from the vertices in CCW order
+    polygon::polygons_intersect(&polygon::ccw(t1), &polygon::ccw(t2))
 }
 
 // Determines if line segments [p, p+r] and [q, q+s] intersect
@@ -236,7 +293,7 @@ pub fn line_segments_intersect(p: Vec2, r: Vec2, q: Vec2, s: Vec2) -> Option<Con
         }
         .normalize();
 
-        Some(Contact { point: c, normal })
+        Some(Contact::new(c, normal, 0.0))
     } else {
         None
     }
@@ -257,15 +314,7 @@ fn point_in_transformed_triangles(
                 .truncate(),
             [a, b, c],
         ) {
-            return Some(Contact {
-                point: triangles_transform
-                    .transform_point(contact.point.extend(0.0))
-                    .truncate(),
-                normal: triangles_transform
-                    .rotation
-                    .mul_vec3(contact.normal.extend(0.0))
-                    .truncate(),
-            });
+            return Some(contact.transformed(triangles_transform));
         }
     }
 
@@ -300,11 +349,7 @@ fn transformed_triangles_intersect(
         let mut iter2 = vertices2.chunks_exact(3);
         while let Some(&[a2, b2, c2]) = iter2.next() {
             if let Some(contact) = triangles_intersect([a1, b1, c1], [a2, b2, c2]) {
-                return Some(Contact {
-                    point: t2.transform_point(contact.point.extend(0.0)).truncate(),
-                    normal: t2.rotation.mul_vec3(contact.normal.extend(0.0)).truncate(),
-                    // normal: (t1.translation - t2.translation).truncate().normalize(),
-                });
+                return Some(contact.transformed(t2));
             }
         }
     }
@@ -329,15 +374,7 @@ fn disk_intersects_transformed_triangles(
             radius,
             [a, b, c],
         ) {
-            return Some(Contact {
-                point: triangles_transform
-                    .transform_point(contact.point.extend(0.0))
-                    .truncate(),
-                normal: triangles_transform
-                    .rotation
-                    .mul_vec3(contact.normal.extend(0.0))
-                    .truncate(),
-            });
+            return Some(contact.transformed(triangles_transform));
         }
     }
 
@@ -346,11 +383,13 @@ fn disk_intersects_transformed_triangles(
 
 fn disks_intersect(c1: Vec2, r1: f32, c2: Vec2, r2: f32) -> Option<Contact> {
     if c1.distance(c2) <= r1 + r2 {
-        let normal = (c1 - c2).normalize();
-        Some(Contact {
-            point: c2 + r2 * normal,
+        // Disks with the same center are pulled apart along x
+        let normal = (c1 - c2).try_normalize().unwrap_or(Vec2::X);
+        Some(Contact::new(
+            c2 + r2 * normal,
             normal,
-        })
+            r1 + r2 - c1.distance(c2),
+        ))
     } else {
         None
     }
@@ -373,17 +412,20 @@ pub fn intersection(
     }
 
     match (t1, t2, &c1.topology, &c2.topology) {
-        (_, _, Topology::Point, Topology::Point) => Some(Contact {
-            point: t1.translation.truncate(),
-            normal: Vec2::ZERO,
-        }),
+        // Points have no normal to bounce along
+        (_, _, Topology::Point, Topology::Point) => None,
         (point, _, Topology::Point, Topology::Disk { radius })
         | (_, point, Topology::Disk { radius }, Topology::Point) => {
             if t1.translation.distance(t2.translation) < *radius {
-                Some(Contact {
-                    point: point.translation.truncate(),
-                    normal: (t1.translation - t2.translation).truncate().normalize(),
-                })
+                Some(Contact::new(
+                    point.translation.truncate(),
+                    // A point at the center of the disk is pushed out along x
+                    (t1.translation - t2.translation)
+                        .truncate()
+                        .try_normalize()
+                        .unwrap_or(Vec2::X),
+                    radius - t1.translation.distance(t2.translation),
+                ))
             } else {
                 None
             }
@@ -467,141 +509,287 @@ pub fn intersection(
                 panic!("Cannot access triangle's mesh");
             }
         }
+        (point, polygon, Topology::Point, Topology::ConvexPolygon { vertices })
+        | (polygon, point, Topology::ConvexPolygon { vertices }, Topology::Point) => {
+            let mut maybe_contact = polygon::point_in_polygon(
+                point.translation.truncate(),
+                &polygon::transformed(polygon, vertices),
+            );
+            if let Topology::ConvexPolygon { vertices: _ } = c1.topology {
+                if let Some(contact) = maybe_contact.as_mut() {
+                    contact.normal = -contact.normal;
+                }
+            }
+            maybe_contact
+        }
+        (disk, polygon, Topology::Disk { radius }, Topology::ConvexPolygon { vertices })
+        | (polygon, disk, Topology::ConvexPolygon { vertices }, Topology::Disk { radius }) => {
+            let mut maybe_contact = polygon::disk_intersects_polygon(
+                disk.translation.truncate(),
+                *radius,
+                &polygon::transformed(polygon, vertices),
+            );
+            if let Topology::ConvexPolygon { vertices: _ } = c1.topology {
+                if let Some(contact) = maybe_contact.as_mut() {
+                    contact.normal = -contact.normal;
+                }
+            }
+            maybe_contact
+        }
+        (
+            triangles,
+            polygon,
+            Topology::Triangles { mesh_handle },
+            Topology::ConvexPolygon { vertices },
+        )
+        | (
+            polygon,
+            triangles,
+            Topology::ConvexPolygon { vertices },
+            Topology::Triangles { mesh_handle },
+        ) => {
+            if let Some(VertexAttributeValues::Float32x3(triangles_vertices)) = meshes
+                .unwrap()
+                .get(&mesh_handle.0)
+                .unwrap()
+                .attribute(Mesh::ATTRIBUTE_POSITION)
+            {
+                let polygon = polygon::transformed(polygon, vertices);
+                let mut iter = triangles_vertices.chunks_exact(3);
+                while let Some(&[a, b, c]) = iter.next() {
+                    // Mesh triangles are not necessarily CCW
+                    let triangle =
+                        polygon::ccw([a, b, c].map(|vertex| {
+                            triangles.transform_point(Vec3::from(vertex)).truncate()
+                        }));
+                    let maybe_contact = if let Topology::Triangles { mesh_handle: _ } = c1.topology
+                    {
+                        polygon::polygons_intersect(&triangle, &polygon)
+                    } else {
+                        polygon::polygons_intersect(&polygon, &triangle)
+                    };
+                    if maybe_contact.is_some() {
+                        return maybe_contact;
+                    }
+                }
+                None
+            } else {
+                panic!("Cannot access triangle's mesh");
+            }
+        }
+        (
+            _,
+            _,
+            Topology::ConvexPolygon {
+                vertices: vertices1,
+            },
+            Topology::ConvexPolygon {
+                vertices: vertices2,
+            },
+        ) => polygon::polygons_intersect(
+            &polygon::transformed(t1, vertices1),
+            &polygon::transformed(t2, vertices2),
+        ),
     }
 }
 
-pub fn intersection_at<'a, I, J>(
-    transform1: &mut Transform, // transform of entity 1 at time
+// Determines if part 1, moving by `motion` relative to part 2 during the tick, hits part 2 on the way,
+// given their transforms at the end of the tick.
+// Returns the fraction of the tick elapsed at the first hit and the contact then.
+//
+// Only points and disks are swept, against shapes assumed not to rotate meanwhile.
+pub fn swept_intersection(
+    t1: Transform,
+    t2: Transform,
+    c1: &Collider,
+    c2: &Collider,
+    motion: Vec2,
+    meshes: Option<Res<Assets<Mesh>>>,
+) -> Option<(f32, Contact)> {
+    let radius = |topology: &Topology| match topology {
+        Topology::Point => Some(0.0),
+        Topology::Disk { radius } => Some(*radius),
+        _ => None,
+    };
+
+    if let Some(r) = radius(&c1.topology) {
+        disk_sweeps(
+            t1.translation.truncate() - motion,
+            motion,
+            r,
+            t2,
+            &c2.topology,
+            meshes,
+        )
+    } else if let Some(r) = radius(&c2.topology) {
+        disk_sweeps(
+            t2.translation.truncate() + motion,
+            -motion,
+            r,
+            t1,
+            &c1.topology,
+            meshes,
+        )
+        .map(|(s, contact)| {
+            (
+                s,
+                Contact {
+                    normal: -contact.normal,
+                    ..contact
+                },
+            )
+        })
+    } else {
+        None
+    }
+}
+
+// Sweeps the disk of center p and radius r by d against the shape
+fn disk_sweeps(
+    p: Vec2,
+    d: Vec2,
+    r: f32,
+    transform: Transform,
+    topology: &Topology,
+    meshes: Option<Res<Assets<Mesh>>>,
+) -> Option<(f32, Contact)> {
+    match topology {
+        Topology::Point if r > 0.0 => {
+            sweep::disk_against_disk(p, d, r, transform.translation.truncate(), 0.0)
+        }
+        Topology::Point => None,
+        Topology::Disk { radius } => {
+            sweep::disk_against_disk(p, d, r, transform.translation.truncate(), *radius)
+        }
+        Topology::Triangles { mesh_handle } => {
+            if let Some(VertexAttributeValues::Float32x3(vertices)) = meshes
+                .unwrap()
+                .get(&mesh_handle.0)
+                .unwrap()
+                .attribute(Mesh::ATTRIBUTE_POSITION)
+            {
+                vertices
+                    .chunks_exact(3)
+                    .filter_map(|triangle| {
+                        // Mesh triangles are not necessarily CCW
+                        let triangle =
+                            polygon::ccw([triangle[0], triangle[1], triangle[2]].map(|vertex| {
+                                transform.transform_point(Vec3::from(vertex)).truncate()
+                            }));
+                        sweep::disk_against_polygon(p, d, r, &triangle)
+                    })
+                    .min_by(|(s1, _), (s2, _)| s1.total_cmp(s2))
+            } else {
+                panic!("Cannot access triangle's mesh");
+            }
+        }
+        Topology::ConvexPolygon { vertices } => {
+            sweep::disk_against_polygon(p, d, r, &polygon::transformed(transform, vertices))
+        }
+    }
+}
+
+// Determines if two bodies intersect, given their transforms and their parts.
+// Returns the deepest contact between their parts along with these parts.
+pub fn bodies_intersect<F1: ReadOnlyWorldQuery, F2: ReadOnlyWorldQuery>(
+    transform1: Transform,
+    transform2: Transform,
+    query_part1: &Query<(&Collider, Entity, &Transform), F1>,
+    query_part2: &Query<(&Collider, Entity, &Transform), F2>,
+    children1: &Children,
+    children2: &Children,
+    meshes: Res<Assets<Mesh>>,
+) -> Option<(Contact, Entity, Entity)> {
+    let mut maybe_collision: Option<(Contact, Entity, Entity)> = None;
+    for (collider1p, entity1p, transform1p) in children1
+        .iter()
+        .filter_map(|child| query_part1.get(*child).ok())
+    {
+        for (collider2p, entity2p, transform2p) in children2
+            .iter()
+            .filter_map(|child| query_part2.get(*child).ok())
+        {
+            if let Some(contact) = intersection(
+                transform::global_of(*transform1p, transform1),
+                transform::global_of(*transform2p, transform2),
+                collider1p,
+                collider2p,
+                Some(Res::clone(&meshes)),
+            ) {
+                if maybe_collision.is_none_or(|(deepest, _, _)| contact.depth > deepest.depth) {
+                    maybe_collision = Some((contact, entity1p, entity2p));
+                }
+            }
+        }
+    }
+
+    maybe_collision
+}
+
+// Determines if two bodies collide during the tick, given their transforms at the end of it.
+//
+// Bodies found apart are swept along their relative motion so that fast ones cannot go through each other.
+// If they hit on the way, they are moved back to where they first touched.
+pub fn bodies_collide<F1: ReadOnlyWorldQuery, F2: ReadOnlyWorldQuery>(
+    transform1: &mut Transform,
     transform2: &mut Transform,
-    time: &mut f32, // time at which the transforms are taken
-    mass1: Mass,
-    mass2: Mass,
-    moment_of_inertia1: MomentOfInertia,
-    moment_of_inertia2: MomentOfInertia,
     velocity1: Velocity,
     velocity2: Velocity,
-    angular_velocity1: AngularVelocity,
-    angular_velocity2: AngularVelocity,
-    parts1: I,
-    parts2: J,
+    delta: f32,
+    query_part1: &Query<(&Collider, Entity, &Transform), F1>,
+    query_part2: &Query<(&Collider, Entity, &Transform), F2>,
     children1: &Children,
     children2: &Children,
     meshes: Res<Assets<Mesh>>,
-) -> Option<(Contact, Entity, Entity)>
-where
-    I: Copy + IntoIterator<Item = (&'a Collider, Entity, &'a Transform)>,
-    J: Copy + IntoIterator<Item = (&'a Collider, Entity, &'a Transform)>,
-{
-    let mut maybe_collision_c: Option<(Contact, Entity, Entity)> = None;
-    'outer: for (collider1p, entity1p, transform1p) in parts1.into_iter() {
-        if !children1.contains(&entity1p) {
-            continue;
-        }
-        for (collider2p, entity2p, transform2p) in parts2.into_iter() {
-            if !children2.contains(&entity2p) {
-                continue;
-            }
-            if let Some(contact_c) = intersection(
+) -> Option<(Contact, Entity, Entity)> {
+    if let Some(collision) = bodies_intersect(
+        *transform1,
+        *transform2,
+        query_part1,
+        query_part2,
+        children1,
+        children2,
+        Res::clone(&meshes),
+    ) {
+        return Some(collision);
+    }
+
+    let motion = (velocity1.0 - velocity2.0).truncate() * delta;
+    let mut maybe_hit: Option<(f32, Contact, Entity, Entity)> = None;
+    for (collider1p, entity1p, transform1p) in children1
+        .iter()
+        .filter_map(|child| query_part1.get(*child).ok())
+    {
+        for (collider2p, entity2p, transform2p) in children2
+            .iter()
+            .filter_map(|child| query_part2.get(*child).ok())
+        {
+            if let Some((s, contact)) = swept_intersection(
                 transform::global_of(*transform1p, *transform1),
                 transform::global_of(*transform2p, *transform2),
                 collider1p,
                 collider2p,
+                motion,
                 Some(Res::clone(&meshes)),
             ) {
-                maybe_collision_c = Some((contact_c, entity1p, entity2p));
-                break 'outer;
-            }
-        }
-    }
-
-    if let Some((contact_c, _, _)) = maybe_collision_c {
-        let [mut time_a, mut time_c] = [0.0, *time];
-        let [mut transform1_a, mut transform2_a] = [
-            transform::at(-time_c, *transform1, velocity1, angular_velocity1),
-            transform::at(-time_c, *transform2, velocity2, angular_velocity2),
-        ];
-        let [mut transform1_c, mut transform2_c] = [*transform1, *transform2];
-
-        let [mut v1, mut v2] = [velocity1, velocity2];
-        let [mut w1, mut w2] = [angular_velocity1, angular_velocity2];
-        response::compute_velocities(
-            &mut v1,
-            &mut v2,
-            &mut w1,
-            &mut w2,
-            transform1_c,
-            transform2_c,
-            mass1,
-            mass2,
-            moment_of_inertia1,
-            moment_of_inertia2,
-            contact_c,
-        );
-        debug!(
-            "\nCollision detected at time tc\n\
-             translation1_a = {}, translation2_a = {}\n\
-             translation1_c = {}, translation2_c = {}\n\
-	     velocity1_c = {}, velocity2_c = {}\n\
-             ta = {}, tc = {}, contact = {:?}",
-            transform1_a.translation,
-            transform2_a.translation,
-            transform1_c.translation,
-            transform2_c.translation,
-            v1.0,
-            v2.0,
-            time_a,
-            time_c,
-            contact_c
-        );
-
-        const EPSILON: f32 = 0.001;
-        while time_c - time_a > EPSILON {
-            let time_b = (time_a + time_c) / 2.0;
-            let [transform1_b, transform2_b] = [
-                transform::at(time_b - time_a, transform1_a, velocity1, angular_velocity1),
-                transform::at(time_b - time_a, transform2_a, velocity2, angular_velocity2),
-            ];
-
-            let mut maybe_collision_b: Option<(Contact, Entity, Entity)> = None;
-            'outer: for (collider1p, entity1p, transform1p) in parts1.into_iter() {
-                if !children1.contains(&entity1p) {
-                    continue;
+                if maybe_hit.is_none_or(|(first, _, _, _)| s < first) {
+                    maybe_hit = Some((s, contact, entity1p, entity2p));
                 }
-                for (collider2p, entity2p, transform2p) in parts2.into_iter() {
-                    if !children2.contains(&entity2p) {
-                        continue;
-                    }
-                    if let Some(contact_b) = intersection(
-                        transform::global_of(*transform1p, transform1_b),
-                        transform::global_of(*transform2p, transform2_b),
-                        collider1p,
-                        collider2p,
-                        Some(Res::clone(&meshes)),
-                    ) {
-                        maybe_collision_b = Some((contact_b, entity1p, entity2p));
-                        break 'outer;
-                    }
-                }
-            }
-
-            if maybe_collision_b.is_some() {
-                maybe_collision_c = maybe_collision_b;
-                [transform1_c, transform2_c] = [transform1_b, transform2_b];
-                time_c = time_b;
-            } else {
-                [transform1_a, transform2_a] = [transform1_b, transform2_b];
-                time_a = time_b;
             }
-
-            debug!(
-                "\nta = {}, tc = {}, contact = {:?}",
-                time_a, time_c, contact_c
-            );
         }
-
-        [*transform1, *transform2] = [transform1_c, transform2_c];
-        *time = time_c;
     }
 
-    maybe_collision_c
+    maybe_hit.map(|(s, mut contact, entity1p, entity2p)| {
+        let rewind = (1.0 - s) * delta;
+        transform1.translation -= velocity1.0 * rewind;
+        transform2.translation -= velocity2.0 * rewind;
+        // The contact was found with the second body where it ends the tick, so it is moved back
+        // along with it. Its depth stays zero since the bodies just touch at the time of impact.
+        let back = velocity2.0.truncate() * rewind;
+        contact.point -= back;
+        contact.point2 = contact.point2.map(|point2| point2 - back);
+        debug_assert_eq!(contact.depth, 0.0);
+
+        (contact, entity1p, entity2p)
+    })
 }
@@ -0,0 +1,223 @@
+// Intersection tests against convex polygons, given by their vertices in CCW order,
+// based on the separating axis theorem.
+//
+// Contact normals point from the polygon given last towards the other shape
+// and contact depths measure the penetration along them.
+// Two polygons touching along an edge have two points of contact.
+// https://dyn4j.org/2010/01/sat/
+// https://box2d.org/files/ErinCatto_ContactManifolds_GDC2007.pdf
+use bevy::prelude::*;
+
+use super::{triangle::TriangleXY, Contact};
+
+// Edges of the polygon as pairs of consecutive vertices
+pub(super) fn edges(polygon: &[Vec2]) -> impl Iterator<Item = (Vec2, Vec2)> + '_ {
+    polygon
+        .iter()
+        .copied()
+        .zip(polygon.iter().copied().cycle().skip(1))
+}
+
+// Outward unit normal of edge [ab] of a CCW polygon
+pub(super) fn outward_normal(a: Vec2, b: Vec2) -> Vec2 {
+    -(b - a).perp().normalize()
+}
+
+fn point_of_line_segment_closest_to_point(p: Vec2, a: Vec2, b: Vec2) -> Vec2 {
+    let ab = b - a;
+    let k = ((p - a).dot(ab) / ab.length_squared()).clamp(0.0, 1.0);
+
+    a + k * ab
+}
+
+pub fn transformed(transform: Transform, polygon: &[Vec2]) -> Vec<Vec2> {
+    polygon
+        .iter()
+        .map(|vertex| transform.transform_point(vertex.extend(0.0)).truncate())
+        .collect()
+}
+
+// Vertices of the triangle in CCW order, whichever order it is given in
+pub fn ccw(triangle: impl Into<TriangleXY>) -> [Vec2; 3] {
+    let triangle = triangle.into();
+    let [a, b, c] = triangle.to_array();
+    if triangle.area() < 0.0 {
+        [a, c, b]
+    } else {
+        [a, b, c]
+    }
+}
+
+// Triangles fanning out of the first vertex of the polygon
+pub fn triangles(polygon: &[Vec2]) -> Vec<TriangleXY> {
+    polygon
+        .windows(2)
+        .skip(1)
+        .map(|pair| TriangleXY(polygon[0], pair[0], pair[1]))
+        .collect()
+}
+
+// Determines if point p is in the polygon.
+// The point is pushed out through the closest edge.
+pub fn point_in_polygon(p: Vec2, polygon: &[Vec2]) -> Option<Contact> {
+    let mut contact = Contact::new(p, Vec2::ZERO, f32::INFINITY);
+    for (a, b) in edges(polygon) {
+        let normal = outward_normal(a, b);
+        let depth = (a - p).dot(normal);
+        if depth < 0.0 {
+            return None;
+        }
+        if depth < contact.depth {
+            contact.normal = normal;
+            contact.depth = depth;
+        }
+    }
+
+    Some(contact)
+}
+
+// Determines if the disk of center c and radius r intersects the polygon.
+//
+// If c lies outside the polygon, the only candidate axis left is the one going through c
+// and the point of the polygon closest to c.
+pub fn disk_intersects_polygon(c: Vec2, r: f32, polygon: &[Vec2]) -> Option<Contact> {
+    let mut inside = true;
+    let mut separation = f32::NEG_INFINITY;
+    let mut normal = Vec2::ZERO;
+    for (a, b) in edges(polygon) {
+        let n = outward_normal(a, b);
+        let s = (c - a).dot(n);
+        if s > r {
+            return None;
+        }
+        inside &= s <= 0.0;
+        if s > separation {
+            separation = s;
+            normal = n;
+        }
+    }
+
+    if inside {
+        return Some(Contact::new(
+            c - separation * normal,
+            normal,
+            r - separation,
+        ));
+    }
+
+    let m = edges(polygon)
+        .map(|(a, b)| point_of_line_segment_closest_to_point(c, a, b))
+        .min_by(|m1, m2| c.distance_squared(*m1).total_cmp(&c.distance_squared(*m2)))
+        .unwrap();
+    let distance = c.distance(m);
+    if distance <= r {
+        Some(Contact::new(m, (c - m) / distance, r - distance))
+    } else {
+        None
+    }
+}
+
+// Among the edges of the reference polygon, finds the one the incident polygon penetrates the least.
+// Returns that penetration and the index of the edge
+// or None if one of the edges separates the polygons.
+fn least_penetration(reference: &[Vec2], incident: &[Vec2]) -> Option<(f32, usize)> {
+    let mut least: Option<(f32, usize)> = None;
+    for (i, (a, b)) in edges(reference).enumerate() {
+        let normal = outward_normal(a, b);
+        let separation = incident
+            .iter()
+            .map(|&v| (v - a).dot(normal))
+            .min_by(f32::total_cmp)
+            .unwrap();
+        if separation > 0.0 {
+            return None;
+        }
+        if least.is_none_or(|(depth, _)| -separation < depth) {
+            least = Some((-separation, i));
+        }
+    }
+
+    least
+}
+
+// Keeps the part of line segment [v1v2] lying on the side of the line through o
+// that direction d points to
+fn clip(v1: Vec2, v2: Vec2, o: Vec2, d: Vec2) -> Option<(Vec2, Vec2)> {
+    let [d1, d2] = [(v1 - o).dot(d), (v2 - o).dot(d)];
+    match (d1 >= 0.0, d2 >= 0.0) {
+        (true, true) => Some((v1, v2)),
+        (false, false) => None,
+        _ => {
+            let v = v1 + d1 / (d1 - d2) * (v2 - v1);
+            if d1 >= 0.0 {
+                Some((v1, v))
+            } else {
+                Some((v, v2))
+            }
+        }
+    }
+}
+
+// Points of the incident polygon in contact with edge i of the reference polygon, deepest first.
+//
+// The edge of the incident polygon facing edge i the most is clipped
+// by the lines orthogonal to edge i through its vertices.
+// Only the points of the clipped edge lying behind edge i are in contact.
+fn manifold(reference: &[Vec2], i: usize, incident: &[Vec2]) -> Option<(Vec2, Option<Vec2>)> {
+    let [a, b] = [reference[i], reference[(i + 1) % reference.len()]];
+    let normal = outward_normal(a, b);
+    let tangent = (b - a).normalize();
+
+    let (v1, v2) = edges(incident)
+        .min_by(|(a1, b1), (a2, b2)| {
+            outward_normal(*a1, *b1)
+                .dot(normal)
+                .total_cmp(&outward_normal(*a2, *b2).dot(normal))
+        })
+        .unwrap();
+    let (v1, v2) = clip(v1, v2, a, tangent)?;
+    let (v1, v2) = clip(v1, v2, b, -tangent)?;
+
+    let mut points: Vec<(f32, Vec2)> = [v1, v2]
+        .into_iter()
+        .map(|v| ((v - a).dot(normal), v))
+        .filter(|(separation, _)| *separation <= 0.0)
+        .collect();
+    points.sort_by(|(s1, _), (s2, _)| s1.total_cmp(s2));
+    points.dedup_by(|(_, v1), (_, v2)| v1 == v2);
+
+    let mut points = points.into_iter().map(|(_, v)| v);
+    points.next().map(|point| (point, points.next()))
+}
+
+// Deepest vertex of the incident polygon behind edge i of the reference polygon
+fn deepest_vertex(reference: &[Vec2], i: usize, incident: &[Vec2]) -> Vec2 {
+    let [a, b] = [reference[i], reference[(i + 1) % reference.len()]];
+    let normal = outward_normal(a, b);
+
+    *incident
+        .iter()
+        .min_by(|v1, v2| (**v1 - a).dot(normal).total_cmp(&(**v2 - a).dot(normal)))
+        .unwrap()
+}
+
+pub fn polygons_intersect(polygon1: &[Vec2], polygon2: &[Vec2]) -> Option<Contact> {
+    let (depth2, i2) = least_penetration(polygon2, polygon1)?;
+    let (depth1, i1) = least_penetration(polygon1, polygon2)?;
+
+    let (reference, i, incident, depth, sign) = if depth2 <= depth1 {
+        (polygon2, i2, polygon1, depth2, 1.0)
+    } else {
+        (polygon1, i1, polygon2, depth1, -1.0)
+    };
+    let normal = sign * outward_normal(reference[i], reference[(i + 1) % reference.len()]);
+    let (point, point2) = manifold(reference, i, incident)
+        .unwrap_or_else(|| (deepest_vertex(reference, i, incident), None));
+
+    Some(Contact {
+        point,
+        point2,
+        normal,
+        depth,
+    })
+}
@@ -0,0 +1,70 @@
+// Swept tests of a point or a disk moving along a line segment against a still shape.
+//
+// The disk of center p and radius r moves by d during the tick.
+// The tests return the fraction of the tick elapsed when it first touches the shape
+// and the contact at that time, its normal pointing from the shape towards the disk.
+// They return None if the disk does not touch the shape or touches it from the start,
+// the latter being left to the discrete tests.
+// https://www.realtimerendering.com/intersections.html
+use bevy::prelude::*;
+
+use super::{polygon, Contact};
+
+// The disk touches the disk of center c and radius rc
+// when its center reaches distance r + rc from c.
+// This happens at the smallest root s of |p + s.d - c|² = (r + rc)².
+pub fn disk_against_disk(p: Vec2, d: Vec2, r: f32, c: Vec2, rc: f32) -> Option<(f32, Contact)> {
+    let radius = r + rc;
+    let cp = p - c;
+    let k = cp.length_squared() - radius * radius;
+    let b = cp.dot(d);
+    if k <= 0.0 || b >= 0.0 {
+        return None;
+    }
+
+    let a = d.length_squared();
+    let delta = b * b - a * k;
+    if delta < 0.0 {
+        return None;
+    }
+
+    let s = (-b - delta.sqrt()) / a;
+    if s <= 1.0 {
+        let normal = (p + s * d - c) / radius;
+        Some((s, Contact::new(c + rc * normal, normal, 0.0)))
+    } else {
+        None
+    }
+}
+
+// The disk touches the CCW polygon when its center reaches the polygon inflated by r,
+// i.e. the edges pushed outwards by r joined by arcs of radius r around the vertices.
+pub fn disk_against_polygon(p: Vec2, d: Vec2, r: f32, polygon: &[Vec2]) -> Option<(f32, Contact)> {
+    let mut first: Option<(f32, Contact)> = None;
+    let mut keep_first = |hit: (f32, Contact)| {
+        if first.is_none_or(|(s, _)| hit.0 < s) {
+            first = Some(hit);
+        }
+    };
+
+    for (a, b) in polygon::edges(polygon) {
+        let normal = polygon::outward_normal(a, b);
+        let separation = (p - a).dot(normal) - r;
+        let approach = -d.dot(normal);
+        if separation >= 0.0 && approach > 0.0 && separation <= approach {
+            let s = separation / approach;
+            let point = p + s * d - r * normal;
+            let ab = b - a;
+            if (0.0..=ab.length_squared()).contains(&(point - a).dot(ab)) {
+                keep_first((s, Contact::new(point, normal, 0.0)));
+            }
+        }
+        if r > 0.0 {
+            if let Some(hit) = disk_against_disk(p, d, r, a, 0.0) {
+                keep_first(hit);
+            }
+        }
+    }
+
+    first
+}
@@ -18,9 +18,8 @@ impl Triangle {
         self.xy().area()
     }
 
-    pub fn random_point(&self) -> Vec3 {
+    pub fn random_point(&self, rng: &mut impl Rng) -> Vec3 {
         let [a, b, c] = self.to_array();
-        let mut rng = rand::thread_rng();
         let x = rng.gen_range(0.0..=1.0);
         let y = rng.gen_range(0.0..=1.0 - x);
 
@@ -42,9 +41,8 @@ impl TriangleXY {
         (b - a).perp_dot(c - a) / 2.0 // .abs() unnecessary since triangle is CCW
     }
 
-    pub fn random_point(&self) -> Vec2 {
+    pub fn random_point(&self, rng: &mut impl Rng) -> Vec2 {
         let [a, b, c] = self.to_array();
-        let mut rng = rand::thread_rng();
         let x = rng.gen_range(0.0..=1.0);
         let y = rng.gen_range(0.0..=1.0 - x);
 
@@ -1,26 +1,222 @@
-use bevy::prelude::*;
+use bevy::{ecs::query::ReadOnlyWorldQuery, prelude::*};
+use iyes_loopless::prelude::*;
 
 use crate::{
     component::{
-        AngularVelocity, ColorDamaged, Health, Indestructible, Mass, MomentOfInertia, Part,
-        Velocity,
+        AngularVelocity, Armor, ColorDamaged, CriticalZone, Health, Indestructible, Mass,
+        MomentOfInertia, Part, PhysicsMaterial, Resistances, Velocity,
     },
     fire::Damages,
-    transform,
+    tick,
 };
 
 use super::{
-    damages,
-    detection::{self, Collider},
+    broad_phase::{self, Bounds, Grid},
+    cache::{Cache, Collision},
+    damages::{self, DamageEvent, DamagedPart},
+    detection::{self, Collider, Contact},
     response,
 };
 
+// Bodies are tested again after bouncing as they may then hit bodies already tested.
+// Iterations stop as soon as one of them ends without any bounce.
+const ITERATIONS: usize = 4;
+
+// Collision resolved between two bodies, and the parts of them that touched.
+// The relative speed is that of the contact points along the normal, before the collision,
+// and the damages are those dealt to part1 and part2 respectively, none for a persisting contact.
+#[derive(Clone, Copy, Debug)]
+pub struct CollisionEvent {
+    pub entity1: Entity,
+    pub entity2: Entity,
+    pub part1: Entity,
+    pub part2: Entity,
+    pub contact: Contact,
+    pub relative_speed: f32,
+    pub damages_dealt: [u32; 2],
+}
+
+// Components of a body, as queried by the collision systems
+type Body<'a> = (
+    Mut<'a, AngularVelocity>,
+    &'a Children,
+    Option<&'a Damages>,
+    Entity,
+    &'a Mass,
+    &'a MomentOfInertia,
+    Mut<'a, Transform>,
+    Mut<'a, Velocity>,
+);
+
+fn bounds<F: ReadOnlyWorldQuery>(
+    transform: &Transform,
+    velocity: &Velocity,
+    delta: f32,
+    children: &Children,
+    query_part: &Query<(&Collider, Entity, &Transform), F>,
+) -> Bounds {
+    broad_phase::body_bounds(
+        transform,
+        velocity.0,
+        delta,
+        children
+            .iter()
+            .filter_map(|child| query_part.get(*child).ok())
+            .map(|(collider, _, transform)| (collider, transform)),
+    )
+}
+
+// Resolves the collision of two bodies during the tick, if any: moves them apart,
+// makes them bounce if they are moving towards each other, deals damages to the parts that touched
+// and reports the collision.
+// The parts are only looked up, from their entities, when damages are dealt.
+// Returns whether the bodies bounced.
+fn resolve<'a, F1, F2, D, I>(
+    (
+        mut angular_velocity1,
+        children1,
+        maybe_damages1,
+        entity1,
+        mass1,
+        moment_of_inertia1,
+        mut transform1,
+        mut velocity1,
+    ): Body,
+    (
+        mut angular_velocity2,
+        children2,
+        maybe_damages2,
+        entity2,
+        mass2,
+        moment_of_inertia2,
+        mut transform2,
+        mut velocity2,
+    ): Body,
+    query_part1: &Query<(&Collider, Entity, &Transform), F1>,
+    query_part2: &Query<(&Collider, Entity, &Transform), F2>,
+    damaged_parts: D,
+    query_physics_material: &Query<&PhysicsMaterial>,
+    meshes: &Res<Assets<Mesh>>,
+    materials: &mut Assets<ColorMaterial>,
+    cache: &mut Cache,
+    collision_event: &mut EventWriter<CollisionEvent>,
+    damage_event: &mut EventWriter<DamageEvent>,
+    delta: f32,
+) -> bool
+where
+    F1: ReadOnlyWorldQuery,
+    F2: ReadOnlyWorldQuery,
+    D: FnOnce(Entity, Entity) -> I,
+    I: IntoIterator<Item = DamagedPart<'a>>,
+{
+    let (contact, entity1p, entity2p) = match detection::bodies_collide(
+        &mut transform1,
+        &mut transform2,
+        *velocity1,
+        *velocity2,
+        delta,
+        query_part1,
+        query_part2,
+        children1,
+        children2,
+        Res::clone(meshes),
+    ) {
+        Some(collision) => collision,
+        None => return false,
+    };
+
+    // Bodies only bounce if they are moving towards each other and are not damaged again
+    // by a contact persisting from the previous tick or from a previous iteration
+    let collision = Collision(entity1, entity2);
+    let resolved = cache.resolved(collision);
+    let persisting = resolved || cache.contains(collision);
+    if !resolved {
+        cache.add(collision);
+    }
+
+    let normal_velocity = response::normal_velocity(
+        *velocity1,
+        *velocity2,
+        *angular_velocity1,
+        *angular_velocity2,
+        *transform1,
+        *transform2,
+        contact,
+    );
+    let bounced = normal_velocity < 0.0;
+    if bounced {
+        response::compute_velocities(
+            &mut velocity1,
+            &mut velocity2,
+            &mut angular_velocity1,
+            &mut angular_velocity2,
+            *transform1,
+            *transform2,
+            *mass1,
+            *mass2,
+            *moment_of_inertia1,
+            *moment_of_inertia2,
+            query_physics_material
+                .get(entity1p)
+                .copied()
+                .unwrap_or_default(),
+            query_physics_material
+                .get(entity2p)
+                .copied()
+                .unwrap_or_default(),
+            contact,
+        );
+    }
+    response::correct_positions(&mut transform1, &mut transform2, *mass1, *mass2, contact);
+
+    // Damages are dealt once, when the bodies come into contact
+    let damages_dealt = if persisting {
+        [0; 2]
+    } else {
+        damages::apply(
+            damaged_parts(entity1p, entity2p),
+            [*transform1, *transform2],
+            maybe_damages1,
+            maybe_damages2,
+            *mass1,
+            *mass2,
+            *velocity1,
+            *velocity2,
+            contact.center(),
+            contact.normal,
+            materials,
+            damage_event,
+        )
+    };
+
+    if !resolved {
+        collision_event.send(CollisionEvent {
+            entity1,
+            entity2,
+            part1: entity1p,
+            part2: entity2p,
+            contact,
+            relative_speed: normal_velocity.abs(),
+            damages_dealt,
+        });
+    }
+
+    debug!(
+        "translation1 = {}, translation2 = {}\n\
+         velocity1 = {}, velocity2 = {}\n",
+        transform1.translation, transform2.translation, velocity1.0, velocity2.0,
+    );
+
+    bounced
+}
+
 pub fn with<C: Component>(
     mut query_c: Query<
         (
             &mut AngularVelocity,
             &Children,
             Option<&Damages>,
+            Entity,
             &Mass,
             &MomentOfInertia,
             &mut Transform,
@@ -31,118 +227,58 @@ pub fn with<C: Component>(
     query_c_part: Query<(&Collider, Entity, &Transform), (With<C>, With<Part>)>,
     mut query_c_part_mut: Query<
         (
+            Entity,
             &Handle<ColorMaterial>,
             Option<&ColorDamaged>,
             &mut Health,
             Option<&Indestructible>,
+            Option<&Armor>,
+            Option<&Resistances>,
+            Option<&CriticalZone>,
+            &Transform,
         ),
         (With<C>, With<Part>),
     >,
+    query_physics_material: Query<&PhysicsMaterial>,
     meshes: Res<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
-    time: Res<Time>,
+    mut cache: ResMut<Cache>,
+    mut collision_event: EventWriter<CollisionEvent>,
+    mut damage_event: EventWriter<DamageEvent>,
+    fixed_timesteps: Res<FixedTimesteps>,
 ) {
-    let mut combinations = query_c.iter_combinations_mut();
-    'outer: while let Some(
-        [(
-            mut angular_velocity1,
-            children1,
-            maybe_damages1,
-            mass1,
-            moment_of_inertia1,
-            mut transform1,
-            mut velocity1,
-        ), (
-            mut angular_velocity2,
-            children2,
-            maybe_damages2,
-            mass2,
-            moment_of_inertia2,
-            mut transform2,
-            mut velocity2,
-        )],
-    ) = combinations.fetch_next()
-    {
-        let mut time_c = time.delta_seconds();
-        if let Some((contact, entity1p, entity2p)) = detection::intersection_at(
-            &mut transform1,
-            &mut transform2,
-            &mut time_c,
-            *mass1,
-            *mass2,
-            *moment_of_inertia1,
-            *moment_of_inertia2,
-            *velocity1,
-            *velocity2,
-            *angular_velocity1,
-            *angular_velocity2,
-            &query_c_part,
-            &query_c_part,
-            children1,
-            children2,
-            Res::clone(&meshes),
-        ) {
-            response::compute_velocities(
-                &mut velocity1,
-                &mut velocity2,
-                &mut angular_velocity1,
-                &mut angular_velocity2,
-                *transform1,
-                *transform2,
-                *mass1,
-                *mass2,
-                *moment_of_inertia1,
-                *moment_of_inertia2,
-                contact,
-            );
+    let delta = tick::delta(&fixed_timesteps);
+    for _ in 0..ITERATIONS {
+        let mut bounced = false;
+        let mut grid = Grid::new(broad_phase::CELL_SIZE);
+        let entities: Vec<Entity> = query_c
+            .iter()
+            .map(|(_, children, _, entity, _, _, transform, velocity)| {
+                grid.insert(bounds(transform, velocity, delta, children, &query_c_part));
+                entity
+            })
+            .collect();
 
-            damages::apply(
-                query_c_part_mut.get_many_mut([entity1p, entity2p]).unwrap(),
-                maybe_damages1,
-                maybe_damages2,
-                *mass1,
-                *mass2,
-                *velocity1,
-                *velocity2,
-                contact.normal,
-                materials.as_mut(),
-            );
-
-            if query_c_part_mut
-                .get_component::<Health>(entity1p)
-                .unwrap()
-                .0
-                > 0
-            {
-                *transform1 = transform::at(
-                    time.delta_seconds() - time_c,
-                    *transform1,
-                    *velocity1,
-                    *angular_velocity1,
-                );
-            }
-
-            if query_c_part_mut
-                .get_component::<Health>(entity2p)
-                .unwrap()
-                .0
-                > 0
-            {
-                *transform2 = transform::at(
-                    time.delta_seconds() - time_c,
-                    *transform2,
-                    *velocity2,
-                    *angular_velocity2,
-                );
-            }
-
-            debug!(
-                "translation1_c = {}, translation2_c = {}\n\
-		     velocity1 = {}, velocity2 = {}\n",
-                transform1.translation, transform2.translation, velocity1.0, velocity2.0,
+        for (i, j) in grid.pairs() {
+            let [body1, body2] = query_c.get_many_mut([entities[i], entities[j]]).unwrap();
+            bounced |= resolve(
+                body1,
+                body2,
+                &query_c_part,
+                &query_c_part,
+                |part1, part2| query_c_part_mut.get_many_mut([part1, part2]).unwrap(),
+                &query_physics_material,
+                &meshes,
+                &mut materials,
+                &mut cache,
+                &mut collision_event,
+                &mut damage_event,
+                delta,
             );
+        }
 
-            continue 'outer;
+        if !bounced {
+            break;
         }
     }
 }
@@ -153,6 +289,7 @@ pub fn between<C1: Component, C2: Component>(
             &mut AngularVelocity,
             &Children,
             Option<&Damages>,
+            Entity,
             &Mass,
             &MomentOfInertia,
             &mut Transform,
@@ -165,6 +302,7 @@ pub fn between<C1: Component, C2: Component>(
             &mut AngularVelocity,
             &Children,
             Option<&Damages>,
+            Entity,
             &Mass,
             &MomentOfInertia,
             &mut Transform,
@@ -175,132 +313,92 @@ pub fn between<C1: Component, C2: Component>(
     query_c1_part: Query<(&Collider, Entity, &Transform), (With<C1>, With<Part>)>,
     mut query_c1_part_mut: Query<
         (
+            Entity,
             &Handle<ColorMaterial>,
             Option<&ColorDamaged>,
             &mut Health,
             Option<&Indestructible>,
+            Option<&Armor>,
+            Option<&Resistances>,
+            Option<&CriticalZone>,
+            &Transform,
         ),
         (With<C1>, With<Part>),
     >,
     query_c2_part: Query<(&Collider, Entity, &Transform), (With<C2>, With<Part>, Without<C1>)>,
     mut query_c2_part_mut: Query<
         (
+            Entity,
             &Handle<ColorMaterial>,
             Option<&ColorDamaged>,
             &mut Health,
             Option<&Indestructible>,
+            Option<&Armor>,
+            Option<&Resistances>,
+            Option<&CriticalZone>,
+            &Transform,
         ),
         (With<C2>, With<Part>, Without<C1>),
     >,
+    query_physics_material: Query<&PhysicsMaterial>,
     meshes: Res<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
-    time: Res<Time>,
+    mut cache: ResMut<Cache>,
+    mut collision_event: EventWriter<CollisionEvent>,
+    mut damage_event: EventWriter<DamageEvent>,
+    fixed_timesteps: Res<FixedTimesteps>,
 ) {
-    'outer: for (
-        mut angular_velocity1,
-        children1,
-        maybe_damages1,
-        mass1,
-        moment_of_inertia1,
-        mut transform1,
-        mut velocity1,
-    ) in query_c1.iter_mut()
-    {
-        for (
-            mut angular_velocity2,
-            children2,
-            maybe_damages2,
-            mass2,
-            moment_of_inertia2,
-            mut transform2,
-            mut velocity2,
-        ) in query_c2.iter_mut()
-        {
-            let mut time_c = time.delta_seconds();
-            if let Some((contact, entity1p, entity2p)) = detection::intersection_at(
-                &mut transform1,
-                &mut transform2,
-                &mut time_c,
-                *mass1,
-                *mass2,
-                *moment_of_inertia1,
-                *moment_of_inertia2,
-                *velocity1,
-                *velocity2,
-                *angular_velocity1,
-                *angular_velocity2,
-                &query_c1_part,
-                &query_c2_part,
-                children1,
-                children2,
-                Res::clone(&meshes),
-            ) {
-                response::compute_velocities(
-                    &mut velocity1,
-                    &mut velocity2,
-                    &mut angular_velocity1,
-                    &mut angular_velocity2,
-                    *transform1,
-                    *transform2,
-                    *mass1,
-                    *mass2,
-                    *moment_of_inertia1,
-                    *moment_of_inertia2,
-                    contact,
-                );
-
-                damages::apply(
-                    [
-                        query_c1_part_mut.get_mut(entity1p).unwrap(),
-                        query_c2_part_mut.get_mut(entity2p).unwrap(),
-                    ],
-                    maybe_damages1,
-                    maybe_damages2,
-                    *mass1,
-                    *mass2,
-                    *velocity1,
-                    *velocity2,
-                    contact.normal,
-                    materials.as_mut(),
-                );
-
-                if query_c1_part_mut
-                    .get_component::<Health>(entity1p)
-                    .unwrap()
-                    .0
-                    > 0
-                {
-                    *transform1 = transform::at(
-                        time.delta_seconds() - time_c,
-                        *transform1,
-                        *velocity1,
-                        *angular_velocity1,
-                    );
-                }
-
-                if query_c2_part_mut
-                    .get_component::<Health>(entity2p)
-                    .unwrap()
-                    .0
-                    > 0
-                {
-                    *transform2 = transform::at(
-                        time.delta_seconds() - time_c,
-                        *transform2,
-                        *velocity2,
-                        *angular_velocity2,
-                    );
-                }
+    let delta = tick::delta(&fixed_timesteps);
+    for _ in 0..ITERATIONS {
+        let mut bounced = false;
+        let mut grid = Grid::new(broad_phase::CELL_SIZE);
+        let entities2: Vec<Entity> = query_c2
+            .iter()
+            .map(|(_, children, _, entity, _, _, transform, velocity)| {
+                grid.insert(bounds(transform, velocity, delta, children, &query_c2_part));
+                entity
+            })
+            .collect();
+        if grid.is_empty() {
+            return;
+        }
+        let bodies1: Vec<(Entity, Bounds)> = query_c1
+            .iter()
+            .map(|(_, children, _, entity, _, _, transform, velocity)| {
+                (
+                    entity,
+                    bounds(transform, velocity, delta, children, &query_c1_part),
+                )
+            })
+            .collect();
 
-                debug!(
-                    "translation1_c = {}, translation2_c = {}\n\
-			     velocity1 = {}, velocity2 = {}\n",
-                    transform1.translation, transform2.translation, velocity1.0, velocity2.0,
+        for (entity1, bounds1) in bodies1 {
+            for j in grid.query(bounds1) {
+                bounced |= resolve(
+                    query_c1.get_mut(entity1).unwrap(),
+                    query_c2.get_mut(entities2[j]).unwrap(),
+                    &query_c1_part,
+                    &query_c2_part,
+                    |part1, part2| {
+                        [
+                            query_c1_part_mut.get_mut(part1).unwrap(),
+                            query_c2_part_mut.get_mut(part2).unwrap(),
+                        ]
+                    },
+                    &query_physics_material,
+                    &meshes,
+                    &mut materials,
+                    &mut cache,
+                    &mut collision_event,
+                    &mut damage_event,
+                    delta,
                 );
-
-                continue 'outer;
             }
         }
+
+        if !bounced {
+            break;
+        }
     }
 }
 
@@ -310,6 +408,7 @@ pub fn among<C1: Component, C2: Component>(
             &mut AngularVelocity,
             &Children,
             Option<&Damages>,
+            Entity,
             &Mass,
             &MomentOfInertia,
             &mut Transform,
@@ -320,108 +419,58 @@ pub fn among<C1: Component, C2: Component>(
     query_part: Query<(&Collider, Entity, &Transform), (Or<(With<C1>, With<C2>)>, With<Part>)>,
     mut query_part_mut: Query<
         (
+            Entity,
             &Handle<ColorMaterial>,
             Option<&ColorDamaged>,
             &mut Health,
             Option<&Indestructible>,
+            Option<&Armor>,
+            Option<&Resistances>,
+            Option<&CriticalZone>,
+            &Transform,
         ),
         (Or<(With<C1>, With<C2>)>, With<Part>),
     >,
+    query_physics_material: Query<&PhysicsMaterial>,
     meshes: Res<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
-    time: Res<Time>,
+    mut cache: ResMut<Cache>,
+    mut collision_event: EventWriter<CollisionEvent>,
+    mut damage_event: EventWriter<DamageEvent>,
+    fixed_timesteps: Res<FixedTimesteps>,
 ) {
-    let mut combinations = query.iter_combinations_mut();
-    'outer: while let Some(
-        [(
-            mut angular_velocity1,
-            children1,
-            maybe_damages1,
-            mass1,
-            moment_of_inertia1,
-            mut transform1,
-            mut velocity1,
-        ), (
-            mut angular_velocity2,
-            children2,
-            maybe_damages2,
-            mass2,
-            moment_of_inertia2,
-            mut transform2,
-            mut velocity2,
-        )],
-    ) = combinations.fetch_next()
-    {
-        let mut time_c = time.delta_seconds();
-        if let Some((contact, entity1p, entity2p)) = detection::intersection_at(
-            &mut transform1,
-            &mut transform2,
-            &mut time_c,
-            *mass1,
-            *mass2,
-            *moment_of_inertia1,
-            *moment_of_inertia2,
-            *velocity1,
-            *velocity2,
-            *angular_velocity1,
-            *angular_velocity2,
-            &query_part,
-            &query_part,
-            children1,
-            children2,
-            Res::clone(&meshes),
-        ) {
-            response::compute_velocities(
-                &mut velocity1,
-                &mut velocity2,
-                &mut angular_velocity1,
-                &mut angular_velocity2,
-                *transform1,
-                *transform2,
-                *mass1,
-                *mass2,
-                *moment_of_inertia1,
-                *moment_of_inertia2,
-                contact,
-            );
+    let delta = tick::delta(&fixed_timesteps);
+    for _ in 0..ITERATIONS {
+        let mut bounced = false;
+        let mut grid = Grid::new(broad_phase::CELL_SIZE);
+        let entities: Vec<Entity> = query
+            .iter()
+            .map(|(_, children, _, entity, _, _, transform, velocity)| {
+                grid.insert(bounds(transform, velocity, delta, children, &query_part));
+                entity
+            })
+            .collect();
 
-            damages::apply(
-                query_part_mut.get_many_mut([entity1p, entity2p]).unwrap(),
-                maybe_damages1,
-                maybe_damages2,
-                *mass1,
-                *mass2,
-                *velocity1,
-                *velocity2,
-                contact.normal,
-                materials.as_mut(),
-            );
-
-            if query_part_mut.get_component::<Health>(entity1p).unwrap().0 > 0 {
-                *transform1 = transform::at(
-                    time.delta_seconds() - time_c,
-                    *transform1,
-                    *velocity1,
-                    *angular_velocity1,
-                );
-            }
-
-            if query_part_mut.get_component::<Health>(entity2p).unwrap().0 > 0 {
-                *transform2 = transform::at(
-                    time.delta_seconds() - time_c,
-                    *transform2,
-                    *velocity2,
-                    *angular_velocity2,
-                );
-            }
-
-            debug!(
-                "translation1_c = {}, translation2_c = {}\n\
-		     velocity1 = {}, velocity2 = {}\n",
-                transform1.translation, transform2.translation, velocity1.0, velocity2.0,
+        for (i, j) in grid.pairs() {
+            let [body1, body2] = query.get_many_mut([entities[i], entities[j]]).unwrap();
+            bounced |= resolve(
+                body1,
+                body2,
+                &query_part,
+                &query_part,
+                |part1, part2| query_part_mut.get_many_mut([part1, part2]).unwrap(),
+                &query_physics_material,
+                &meshes,
+                &mut materials,
+                &mut cache,
+                &mut collision_event,
+                &mut damage_event,
+                delta,
             );
+        }
 
-            continue 'outer;
+        if !bounced {
+            break;
         }
     }
 }
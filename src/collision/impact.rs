@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 
-use crate::component::Health;
+use crate::{component::Health, shared_assets::SharedAssets};
 
 #[derive(Component)]
 pub struct Impact;
@@ -16,17 +16,15 @@ pub fn spawn(
     mut commands: Commands,
     mut impact_event: EventReader<ImpactEvent>,
     mut meshes: ResMut<Assets<Mesh>>,
+    mut shared_assets: ResMut<SharedAssets>,
 ) {
     for ev in impact_event.iter() {
         commands
             .spawn(Impact)
             .insert(Health(10))
             .insert(ColorMesh2dBundle {
-                mesh: meshes
-                    .add(Mesh::from(shape::Circle {
-                        radius: ev.radius,
-                        vertices: ev.vertices,
-                    }))
+                mesh: shared_assets
+                    .circle(&mut meshes, ev.radius, ev.vertices)
                     .into(),
                 transform: Transform::from_translation(ev.translation + Vec3::new(0.0, 0.0, 1.0)),
                 material: ev.color.clone(),
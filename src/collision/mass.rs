@@ -0,0 +1,152 @@
+// Mass, centroid and moment of inertia of bodies, derived from the colliders
+// and materials of their parts.
+//
+// Bodies are translated so that their origin, around which they rotate, stays at their centroid:
+// their children are moved the other way so that nothing moves on screen.
+// https://en.wikipedia.org/wiki/List_of_moments_of_inertia
+// https://en.wikipedia.org/wiki/Parallel_axis_theorem
+use bevy::prelude::*;
+use std::{f32::consts::PI, iter::Sum};
+
+use crate::component::{Mass, MomentOfInertia, PhysicsMaterial, Velocity};
+
+use super::detection::{triangle::TriangleXY, Collider, Topology};
+
+// The moment of inertia is about the centroid
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MassProperties {
+    pub mass: f32,
+    pub centroid: Vec2,
+    pub moment_of_inertia: f32,
+}
+
+impl MassProperties {
+    pub fn of_disk(center: Vec2, radius: f32, density: f32) -> MassProperties {
+        let mass = density * PI * radius * radius;
+        MassProperties {
+            mass,
+            centroid: center,
+            moment_of_inertia: 0.5 * mass * radius * radius,
+        }
+    }
+
+    // The triangle can be either CW or CCW
+    pub fn of_triangle(triangle: TriangleXY, density: f32) -> MassProperties {
+        let [a, b, c] = triangle.to_array();
+        let mass = density * triangle.area().abs();
+        let centroid = (a + b + c) / 3.0;
+        let moment_of_inertia_about_origin =
+            mass / 6.0 * (a.dot(a) + b.dot(b) + c.dot(c) + a.dot(b) + b.dot(c) + c.dot(a));
+
+        MassProperties {
+            mass,
+            centroid,
+            moment_of_inertia: moment_of_inertia_about_origin - mass * centroid.length_squared(),
+        }
+    }
+
+    // Points have no mass
+    pub fn of_collider(collider: &Collider, density: f32, meshes: &Assets<Mesh>) -> MassProperties {
+        match collider.topology {
+            Topology::Point => MassProperties::default(),
+            Topology::Disk { radius } => MassProperties::of_disk(Vec2::ZERO, radius, density),
+            Topology::Triangles { .. } | Topology::ConvexPolygon { .. } => collider
+                .topology
+                .triangles(meshes)
+                .into_iter()
+                .map(|triangle| MassProperties::of_triangle(triangle, density))
+                .sum(),
+        }
+    }
+
+    // Properties of the same shape moved by the transform, whose scale is ignored
+    pub fn transformed(&self, transform: Transform) -> MassProperties {
+        MassProperties {
+            centroid: transform
+                .transform_point(self.centroid.extend(0.0))
+                .truncate(),
+            ..*self
+        }
+    }
+}
+
+// Properties of the union of the shapes
+impl Sum for MassProperties {
+    fn sum<I: Iterator<Item = MassProperties>>(iter: I) -> MassProperties {
+        let shapes: Vec<MassProperties> = iter.collect();
+        let mass: f32 = shapes.iter().map(|shape| shape.mass).sum();
+        if mass == 0.0 {
+            return MassProperties::default();
+        }
+
+        let centroid = shapes
+            .iter()
+            .map(|shape| shape.mass * shape.centroid)
+            .sum::<Vec2>()
+            / mass;
+        let moment_of_inertia = shapes
+            .iter()
+            .map(|shape| {
+                shape.moment_of_inertia + shape.mass * shape.centroid.distance_squared(centroid)
+            })
+            .sum();
+
+        MassProperties {
+            mass,
+            centroid,
+            moment_of_inertia,
+        }
+    }
+}
+
+// Computes the mass properties of the bodies whose parts have changed,
+// i.e. of the bodies just spawned and of those which just lost a part.
+// Bodies without any massive part, such as fires, keep the mass they were given.
+pub fn update(
+    mut commands: Commands,
+    mut query: Query<
+        (&Children, Entity, &mut Transform),
+        (Changed<Children>, With<Velocity>, Without<Parent>),
+    >,
+    mut query_child: Query<
+        (Option<&Collider>, Option<&PhysicsMaterial>, &mut Transform),
+        With<Parent>,
+    >,
+    meshes: Res<Assets<Mesh>>,
+) {
+    for (children, entity, mut transform) in &mut query {
+        let properties: MassProperties = children
+            .iter()
+            .filter_map(|child| query_child.get(*child).ok())
+            .filter_map(|(maybe_collider, maybe_material, child_transform)| {
+                maybe_collider.map(|collider| {
+                    MassProperties::of_collider(
+                        collider,
+                        maybe_material.copied().unwrap_or_default().density,
+                        &meshes,
+                    )
+                    .transformed(*child_transform)
+                })
+            })
+            .sum();
+        if properties.mass == 0.0 {
+            continue;
+        }
+
+        commands
+            .entity(entity)
+            .insert(Mass(properties.mass))
+            .insert(MomentOfInertia(properties.moment_of_inertia));
+
+        let centroid = properties.centroid.extend(0.0);
+        if centroid != Vec3::ZERO {
+            let rotation = transform.rotation;
+            transform.translation += rotation * centroid;
+            for child in children {
+                if let Ok((_, _, mut child_transform)) = query_child.get_mut(*child) {
+                    child_transform.translation -= centroid;
+                }
+            }
+        }
+    }
+}
@@ -1,5 +1,8 @@
+pub mod broad_phase;
+pub mod cache;
 pub mod damages;
 pub mod detection;
 pub mod generic;
 pub mod impact;
+pub mod mass;
 pub mod response;
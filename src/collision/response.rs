@@ -5,9 +5,11 @@ use bevy::prelude::*;
 
 use crate::{
     collision::detection::Contact,
-    component::{AngularVelocity, Mass, MomentOfInertia, Velocity},
+    component::{AngularVelocity, Mass, MomentOfInertia, PhysicsMaterial, Velocity},
 };
 
+// Friction adds an impulse along the tangent, bounded by the friction times the normal impulse
+// (Coulomb's law), so that it can slow sliding down to a stop but never reverse it.
 pub fn compute_velocities(
     velocity1: &mut Velocity,
     velocity2: &mut Velocity,
@@ -19,18 +21,28 @@ pub fn compute_velocities(
     mass2: Mass,
     moment_of_inertia1: MomentOfInertia,
     moment_of_inertia2: MomentOfInertia,
+    material1: PhysicsMaterial,
+    material2: PhysicsMaterial,
     contact: Contact,
 ) {
     let [m1, m2] = [mass1.0, mass2.0];
     let [i1, i2] = [moment_of_inertia1.0, moment_of_inertia2.0];
-    let [v1, v2] = [velocity1.0, velocity2.0];
-    let [w1, w2] = [angular_velocity1.0, angular_velocity2.0];
     let n = contact.normal.extend(0.0);
-    let r1 = (contact.point - transform1.translation.truncate()).extend(0.0);
-    let r2 = (contact.point - transform2.translation.truncate()).extend(0.0);
-    const RESTITUTION: f32 = 1.0;
+    let r1 = (contact.center() - transform1.translation.truncate()).extend(0.0);
+    let r2 = (contact.center() - transform2.translation.truncate()).extend(0.0);
+    let restitution = material1.restitution_with(&material2);
+    let friction = material1.friction_with(&material2);
 
-    let j = -(1.0 + RESTITUTION) * ((v1 - v2).dot(n) + (w1 * r1 - w2 * r2).cross(n).z)
+    let j = -(1.0 + restitution)
+        * normal_velocity(
+            *velocity1,
+            *velocity2,
+            *angular_velocity1,
+            *angular_velocity2,
+            transform1,
+            transform2,
+            contact,
+        )
         / (1.0 / m1 + 1.0 / m2 + (r1.cross(n)).z.powi(2) / i1 + (r2.cross(n)).z.powi(2) / i2);
 
     velocity1.0 += j / m1 * n;
@@ -38,4 +50,63 @@ pub fn compute_velocities(
 
     angular_velocity1.0 += j / i1 * r1.cross(n).z;
     angular_velocity2.0 -= j / i2 * r2.cross(n).z;
+
+    let point_velocity1 = velocity1.0 + angular_velocity1.0 * Vec3::Z.cross(r1);
+    let point_velocity2 = velocity2.0 + angular_velocity2.0 * Vec3::Z.cross(r2);
+    let relative_velocity = point_velocity1 - point_velocity2;
+    let t = (relative_velocity - relative_velocity.dot(n) * n).normalize_or_zero();
+    if t == Vec3::ZERO {
+        return;
+    }
+
+    let jt = (-relative_velocity.dot(t)
+        / (1.0 / m1 + 1.0 / m2 + (r1.cross(t)).z.powi(2) / i1 + (r2.cross(t)).z.powi(2) / i2))
+        .clamp(-friction * j, friction * j);
+
+    velocity1.0 += jt / m1 * t;
+    velocity2.0 -= jt / m2 * t;
+
+    angular_velocity1.0 += jt / i1 * r1.cross(t).z;
+    angular_velocity2.0 -= jt / i2 * r2.cross(t).z;
+}
+
+// Velocity of the contact point of body 1 relative to that of body 2, along the normal.
+// It is negative when the bodies are moving towards each other.
+pub fn normal_velocity(
+    velocity1: Velocity,
+    velocity2: Velocity,
+    angular_velocity1: AngularVelocity,
+    angular_velocity2: AngularVelocity,
+    transform1: Transform,
+    transform2: Transform,
+    contact: Contact,
+) -> f32 {
+    let [v1, v2] = [velocity1.0, velocity2.0];
+    let [w1, w2] = [angular_velocity1.0, angular_velocity2.0];
+    let n = contact.normal.extend(0.0);
+    let r1 = (contact.center() - transform1.translation.truncate()).extend(0.0);
+    let r2 = (contact.center() - transform2.translation.truncate()).extend(0.0);
+
+    (v1 - v2).dot(n) + (w1 * r1 - w2 * r2).cross(n).z
+}
+
+// Pushes the bodies apart along the normal, in inverse proportion to their masses,
+// to resolve most of their penetration.
+// A small penetration is tolerated so that resting contacts keep touching from one tick to the next.
+pub fn correct_positions(
+    transform1: &mut Transform,
+    transform2: &mut Transform,
+    mass1: Mass,
+    mass2: Mass,
+    contact: Contact,
+) {
+    const SLOP: f32 = 0.5;
+    const PERCENT: f32 = 0.8;
+
+    let [m1, m2] = [mass1.0, mass2.0];
+    let correction = (contact.depth - SLOP).max(0.0) * PERCENT / (1.0 / m1 + 1.0 / m2)
+        * contact.normal.extend(0.0);
+
+    transform1.translation += correction / m1;
+    transform2.translation -= correction / m2;
 }
@@ -1,23 +1,30 @@
 use bevy::{prelude::*, render::mesh::PrimitiveTopology, text::Text2dBounds};
 
 use crate::{
-    boss::Boss,
     component::Part,
     constant::{WINDOW_HEIGHT, WINDOW_WIDTH},
+    keyboard_bindings::KeyboardBindings,
+    shared_assets::SharedAssets,
     spaceship::Spaceship,
+    waypoint::{Waypoint, WaypointKind},
 };
 
 const FONT: &str = "fonts/FiraSans-Bold.ttf";
 const FONT_SIZE: f32 = 20.0;
 const COLOR: Color = Color::DARK_GRAY;
 // Box at the top right of the screen containing the text of the compass
-const BOX_WIDTH: f32 = 140.0;
+const BOX_WIDTH: f32 = 220.0;
 const BOX_HEIGHT: f32 = FONT_SIZE;
 const BOX_CENTER_LEFT: Vec3 = Vec3 {
     x: WINDOW_WIDTH / 2.0 - BOX_WIDTH,
     y: WINDOW_HEIGHT / 2.0 - BOX_HEIGHT / 2.0,
     z: 0.0,
 };
+// Triangles on the edges of the screen pointing to the waypoints out of sight
+const INDICATOR_RADIUS: f32 = 8.0;
+const INDICATOR_MARGIN: f32 = 12.0;
+// Scale of the indicator of the active waypoint
+const INDICATOR_ACTIVE_SCALE: f32 = 1.5;
 
 #[derive(Component)]
 pub struct Compass;
@@ -25,18 +32,39 @@ pub struct Compass;
 #[derive(Component)]
 pub struct Needle;
 
+#[derive(Component)]
+pub struct EdgeIndicator;
+
+// Waypoint the needle points to, the first one when none was picked or when it is gone
+#[derive(Debug, Default, Resource)]
+pub struct ActiveWaypoint(pub Option<Entity>);
+
+// Waypoint following the current one, looping back to the first
+pub fn next_waypoint(current: Option<Entity>, waypoints: &[Entity]) -> Option<Entity> {
+    match current.and_then(|current| waypoints.iter().position(|w| *w == current)) {
+        Some(i) => waypoints.get((i + 1) % waypoints.len()).copied(),
+        None => waypoints.first().copied(),
+    }
+}
+
+// Point of the border of the rectangle of the given half extents, centered at the origin,
+// in the direction of offset. None if offset lies within the rectangle.
+pub fn edge_point(offset: Vec2, half_extents: Vec2) -> Option<Vec2> {
+    if offset.x.abs() <= half_extents.x && offset.y.abs() <= half_extents.y {
+        return None;
+    }
+
+    Some(offset * (half_extents.x / offset.x.abs()).min(half_extents.y / offset.y.abs()))
+}
+
 pub fn spawn(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
     asset_server: Res<AssetServer>,
-    query_boss: Query<&Transform, (With<Boss>, Without<Part>)>,
     query_camera: Query<&Transform, With<Camera>>,
-    query_spaceship: Query<&Transform, (With<Spaceship>, Without<Part>)>,
 ) {
     let camera = query_camera.single();
-    let boss = query_boss.single();
-    let spaceship = query_spaceship.single();
 
     let text_style = TextStyle {
         font: asset_server.load(FONT),
@@ -77,13 +105,7 @@ pub fn spawn(
         .insert(ColorMesh2dBundle {
             mesh: meshes.add(mesh).into(),
             transform: Transform::from_translation(NEEDLE_POSITION)
-                .with_scale(Vec3::splat(NEEDLE_SCALE))
-                .with_rotation(Quat::from_rotation_arc_2d(
-                    Vec2::X,
-                    (boss.translation - spaceship.translation)
-                        .truncate()
-                        .normalize(),
-                )),
+                .with_scale(Vec3::splat(NEEDLE_SCALE)),
             material: materials.add(COLOR.into()),
             ..default()
         })
@@ -92,10 +114,40 @@ pub fn spawn(
     commands.entity(compass).add_child(needle);
 }
 
+// Waypoints in the order the compass cycles through them
+fn sorted(query_waypoint: &Query<(Entity, &Waypoint)>) -> Vec<Entity> {
+    let mut waypoints: Vec<(WaypointKind, Entity)> = query_waypoint
+        .iter()
+        .map(|(entity, waypoint)| (waypoint.kind, entity))
+        .collect();
+    waypoints.sort();
+
+    waypoints.into_iter().map(|(_, entity)| entity).collect()
+}
+
+pub fn cycle(
+    mut active_waypoint: ResMut<ActiveWaypoint>,
+    input: Res<Input<KeyCode>>,
+    query_bindings: Query<&KeyboardBindings>,
+    query_waypoint: Query<(Entity, &Waypoint)>,
+) {
+    let waypoints = sorted(&query_waypoint);
+    if active_waypoint
+        .0
+        .is_none_or(|active| !waypoints.contains(&active))
+    {
+        active_waypoint.0 = waypoints.first().copied();
+    }
+    if input.just_pressed(query_bindings.single().next_target()) {
+        active_waypoint.0 = next_waypoint(active_waypoint.0, &waypoints);
+    }
+}
+
 pub fn update(
     mut query_compass: Query<(&mut Transform, &mut Text), With<Compass>>,
     mut query_needle: Query<&mut Transform, (With<Needle>, Without<Compass>)>,
-    query_boss: Query<&Transform, (With<Boss>, Without<Part>, Without<Compass>, Without<Needle>)>,
+    active_waypoint: Res<ActiveWaypoint>,
+    query_waypoint: Query<(&Waypoint, &Transform), (Without<Compass>, Without<Needle>)>,
     query_camera: Query<&Transform, (With<Camera>, Without<Compass>, Without<Needle>)>,
     query_spaceship: Query<
         &Transform,
@@ -111,12 +163,88 @@ pub fn update(
     let (mut compass, mut text) = query_compass.single_mut();
     compass.translation = camera.translation + BOX_CENTER_LEFT;
     if let Ok(spaceship) = query_spaceship.get_single() {
-        if let Ok(boss) = query_boss.get_single() {
-            let trajectory = (boss.translation - spaceship.translation).truncate();
-            let mut needle = query_needle.single_mut();
-            needle.rotation = Quat::from_rotation_arc_2d(Vec2::X, trajectory.normalize());
-            let distance = trajectory.length();
-            text.sections[0].value = format!("Target: {:<7.0}", distance);
+        match active_waypoint
+            .0
+            .and_then(|active| query_waypoint.get(active).ok())
+        {
+            Some((waypoint, transform)) => {
+                let trajectory = (transform.translation - spaceship.translation).truncate();
+                let mut needle = query_needle.single_mut();
+                needle.rotation =
+                    Quat::from_rotation_arc_2d(Vec2::X, trajectory.normalize_or_zero());
+                let distance = trajectory.length();
+                text.sections[0].value = format!("{}: {:<7.0}", waypoint.label, distance);
+            }
+            None => text.sections[0].value = String::new(),
         }
     }
 }
+
+// Indicators are recycled from one frame to the next, those left over being hidden.
+// New ones are hidden until the next frame, once their transforms have been propagated.
+pub fn update_indicators(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut shared_assets: ResMut<SharedAssets>,
+    mut query_indicator: Query<
+        (&mut Handle<ColorMaterial>, &mut Transform, &mut Visibility),
+        With<EdgeIndicator>,
+    >,
+    active_waypoint: Res<ActiveWaypoint>,
+    query_camera: Query<(Entity, &Transform), (With<Camera>, Without<EdgeIndicator>)>,
+    query_waypoint: Query<(Entity, &Waypoint, &Transform), Without<EdgeIndicator>>,
+) {
+    let (camera, c_transform) = query_camera.single();
+    let half_extents = Vec2::new(
+        WINDOW_WIDTH / 2.0 - INDICATOR_MARGIN,
+        WINDOW_HEIGHT / 2.0 - INDICATOR_MARGIN,
+    );
+
+    let mut indicators = query_indicator.iter_mut();
+    for (entity, waypoint, transform) in &query_waypoint {
+        let offset = (transform.translation - c_transform.translation).truncate();
+        let position = match edge_point(offset, half_extents) {
+            Some(position) => position,
+            None => continue,
+        };
+        let material = shared_assets.material(&mut materials, waypoint.kind.color());
+        let scale = if active_waypoint.0 == Some(entity) {
+            INDICATOR_ACTIVE_SCALE
+        } else {
+            1.0
+        };
+        let transform = Transform::from_translation(position.extend(0.0))
+            .with_rotation(Quat::from_rotation_arc_2d(Vec2::Y, offset.normalize()))
+            .with_scale(Vec3::splat(scale));
+
+        match indicators.next() {
+            Some((mut i_material, mut i_transform, mut visibility)) => {
+                if *i_material != material {
+                    *i_material = material;
+                }
+                *i_transform = transform;
+                visibility.is_visible = true;
+            }
+            None => {
+                let indicator = commands
+                    .spawn(EdgeIndicator)
+                    .insert(ColorMesh2dBundle {
+                        // A circle of 3 vertices is a triangle pointing up
+                        mesh: shared_assets
+                            .circle(&mut meshes, INDICATOR_RADIUS, 3)
+                            .into(),
+                        transform,
+                        material,
+                        visibility: Visibility { is_visible: false },
+                        ..default()
+                    })
+                    .id();
+                commands.entity(camera).add_child(indicator);
+            }
+        }
+    }
+    for (_, _, mut visibility) in indicators {
+        visibility.is_visible = false;
+    }
+}
@@ -3,7 +3,7 @@ use bevy::prelude::*;
 #[derive(Clone, Component, Copy)]
 pub struct Part;
 
-#[derive(Clone, Component, Copy)]
+#[derive(Clone, Component, Copy, Debug)]
 pub struct Health(pub u32);
 
 #[derive(Clone, Component, Copy)]
@@ -26,3 +26,89 @@ pub struct ColorDamaged(pub Color);
 
 #[derive(Component)]
 pub struct Attack(pub Vec3);
+
+// What a part is made of.
+// When two parts collide, the bounciest of the two sets the restitution
+// and their frictions are combined by geometric mean.
+#[derive(Clone, Component, Copy, Debug, PartialEq)]
+pub struct PhysicsMaterial {
+    pub restitution: f32,
+    pub friction: f32,
+    pub density: f32,
+}
+
+impl PhysicsMaterial {
+    pub const ROCK: PhysicsMaterial = PhysicsMaterial {
+        restitution: 0.2,
+        friction: 0.8,
+        density: 1.5,
+    };
+    pub const HULL: PhysicsMaterial = PhysicsMaterial {
+        restitution: 0.5,
+        friction: 0.4,
+        density: 1.0,
+    };
+    pub const SHIELD: PhysicsMaterial = PhysicsMaterial {
+        restitution: 1.0,
+        friction: 0.0,
+        density: 1.0,
+    };
+
+    pub fn restitution_with(&self, other: &PhysicsMaterial) -> f32 {
+        self.restitution.max(other.restitution)
+    }
+
+    pub fn friction_with(&self, other: &PhysicsMaterial) -> f32 {
+        (self.friction * other.friction).sqrt()
+    }
+}
+
+// Parts without a material bounce perfectly and slide without friction.
+impl Default for PhysicsMaterial {
+    fn default() -> Self {
+        PhysicsMaterial {
+            restitution: 1.0,
+            friction: 0.0,
+            density: 1.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DamageKind {
+    // Dealt by bodies running into each other
+    Kinetic,
+    // Dealt by fires
+    Energy,
+    Explosive,
+}
+
+// Damages taken by a part are reduced by its resistance to their kind, then by its armor
+#[derive(Clone, Component, Copy)]
+pub struct Armor(pub u32);
+
+// Fractions of the damages of each kind a part shrugs off, between 0 and 1
+#[derive(Clone, Component, Copy, Default)]
+pub struct Resistances {
+    pub kinetic: f32,
+    pub energy: f32,
+    pub explosive: f32,
+}
+
+impl Resistances {
+    pub fn to(&self, kind: DamageKind) -> f32 {
+        match kind {
+            DamageKind::Kinetic => self.kinetic,
+            DamageKind::Energy => self.energy,
+            DamageKind::Explosive => self.explosive,
+        }
+    }
+}
+
+// Disk of a part, in its own coordinates, where hits deal multiplied damages
+#[derive(Clone, Component, Copy)]
+pub struct CriticalZone {
+    pub center: Vec2,
+    pub radius: f32,
+    pub multiplier: f32,
+}
@@ -5,3 +5,11 @@ pub const WINDOW_HEIGHT: f32 = 720.0;
 // pub const WINDOW_WIDTH: f32 = 800.0;
 // pub const WINDOW_HEIGHT: f32 = 600.0;
 pub const WINDOW_Z: f32 = 500.0;
+
+// Duration of a tick of the simulation, in seconds
+pub const TIMESTEP: f32 = 1.0 / 60.0;
+
+// Velocities of the boss and the intercepters decay exponentially at these rates, in s⁻¹.
+// They used to lose 5% and 25% of them each frame at 60 fps: -ln(0.95) * 60 and -ln(0.75) * 60.
+pub const DRAG: f32 = 3.1;
+pub const ANGULAR_DRAG: f32 = 17.3;
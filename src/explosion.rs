@@ -0,0 +1,117 @@
+use bevy::prelude::*;
+
+use crate::{
+    blast::BlastEvent,
+    collision::damages::{self, DamageEvent},
+    component::{
+        AngularVelocity, Armor, ColorDamaged, CriticalZone, DamageKind, Health, Indestructible,
+        Mass, MomentOfInertia, Part, Resistances, Velocity,
+    },
+    fire::Fire,
+    transform,
+};
+
+const COLOR: Color = Color::ORANGE_RED;
+const VERTICES: usize = 32;
+
+// Part exploding when destroyed.
+// Damages and impulse are those received at the center of the explosion
+// and fall off linearly down to zero at its radius.
+#[derive(Clone, Component, Copy)]
+pub struct Explosive {
+    pub radius: f32,
+    pub damages: f32,
+    pub impulse: f32,
+}
+
+pub struct ExplosionEvent {
+    pub explosive: Explosive,
+    pub translation: Vec3,
+}
+
+pub fn explode(
+    mut blast_event: EventWriter<BlastEvent>,
+    mut damage_event: EventWriter<DamageEvent>,
+    mut explosion_event: EventReader<ExplosionEvent>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut query: Query<
+        (
+            &mut AngularVelocity,
+            &Children,
+            &Mass,
+            &MomentOfInertia,
+            &Transform,
+            &mut Velocity,
+        ),
+        (Without<Fire>, Without<Part>),
+    >,
+    mut query_part: Query<
+        (
+            Entity,
+            &Handle<ColorMaterial>,
+            Option<&ColorDamaged>,
+            &mut Health,
+            Option<&Indestructible>,
+            Option<&Armor>,
+            Option<&Resistances>,
+            Option<&CriticalZone>,
+            &Transform,
+        ),
+        With<Part>,
+    >,
+) {
+    for ev in explosion_event.iter() {
+        let Explosive {
+            radius,
+            damages,
+            impulse,
+        } = ev.explosive;
+
+        blast_event.send(BlastEvent {
+            radius,
+            vertices: VERTICES,
+            color: COLOR,
+            translation: ev.translation,
+        });
+
+        for (mut angular_velocity, children, mass, moment_of_inertia, transform, mut velocity) in
+            &mut query
+        {
+            for child in children {
+                let part = match query_part.get_mut(*child) {
+                    Ok(part) => part,
+                    Err(_) => continue,
+                };
+                let (.., part_transform) = &part;
+                let translation = transform::global_of(**part_transform, *transform).translation;
+                let distance = translation.truncate().distance(ev.translation.truncate());
+                if distance >= radius {
+                    continue;
+                }
+                let falloff = 1.0 - distance / radius;
+
+                // Parts are pushed away from the center of the explosion,
+                // and spun if it is not aligned with the centroid of their body
+                let j = impulse
+                    * falloff
+                    * (translation - ev.translation)
+                        .truncate()
+                        .normalize_or_zero()
+                        .extend(0.0);
+                let r = translation - transform.translation;
+                velocity.0 += j / mass.0;
+                angular_velocity.0 += r.cross(j).z / moment_of_inertia.0;
+
+                damages::deal(
+                    part,
+                    *transform,
+                    damages * falloff,
+                    DamageKind::Explosive,
+                    translation.truncate(),
+                    materials.as_mut(),
+                    &mut damage_event,
+                );
+            }
+        }
+    }
+}
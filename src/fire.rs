@@ -1,11 +1,14 @@
 use bevy::prelude::*;
+use iyes_loopless::prelude::*;
 
 use crate::{
     collision::{
         detection::{Aabb, Collider, Topology},
         impact::ImpactEvent,
     },
-    component::{AngularVelocity, Health, Mass, MomentOfInertia, Part, Velocity},
+    component::{AngularVelocity, DamageKind, Health, Mass, MomentOfInertia, Part, Velocity},
+    shared_assets::SharedAssets,
+    tick,
 };
 
 #[derive(Component)]
@@ -18,7 +21,10 @@ pub struct Fire {
 pub struct Enemy;
 
 #[derive(Component)]
-pub struct Damages(pub u32);
+pub struct Damages {
+    pub amount: u32,
+    pub kind: DamageKind,
+}
 
 pub struct FireEvent {
     pub fire: Fire,
@@ -37,6 +43,7 @@ pub fn spawn(
     mut fire_event: EventReader<FireEvent>,
     mut materials: ResMut<Assets<ColorMaterial>>,
     mut meshes: ResMut<Assets<Mesh>>,
+    mut shared_assets: ResMut<SharedAssets>,
 ) {
     for ev in fire_event.iter() {
         let fire = commands
@@ -44,7 +51,10 @@ pub fn spawn(
                 impact_radius: ev.fire.impact_radius,
                 impact_vertices: ev.fire.impact_vertices,
             })
-            .insert(Damages(ev.damages))
+            .insert(Damages {
+                amount: ev.damages,
+                kind: DamageKind::Energy,
+            })
             .insert(Mass(1.0))
             .insert(MomentOfInertia(1.0))
             .insert(ev.velocity)
@@ -72,13 +82,10 @@ pub fn spawn(
                 topology: Topology::Point,
             })
             .insert(ColorMesh2dBundle {
-                mesh: meshes
-                    .add(Mesh::from(shape::Circle {
-                        radius: ev.radius,
-                        vertices: ev.vertices,
-                    }))
+                mesh: shared_assets
+                    .circle(&mut meshes, ev.radius, ev.vertices)
                     .into(),
-                material: materials.add(ev.color.into()),
+                material: shared_assets.material(&mut materials, ev.color),
                 ..Default::default()
             })
             .id();
@@ -89,12 +96,12 @@ pub fn spawn(
 
 pub fn movement(
     mut query: Query<(&mut Transform, &Velocity, &AngularVelocity), With<Fire>>,
-    time: Res<Time>,
+    fixed_timesteps: Res<FixedTimesteps>,
 ) {
+    let delta = tick::delta(&fixed_timesteps);
     for (mut transform, velocity, angular_velocity) in query.iter_mut() {
-        transform.translation += velocity.0 * time.delta_seconds();
-        transform.rotation *=
-            Quat::from_axis_angle(Vec3::Z, angular_velocity.0 * time.delta_seconds());
+        transform.translation += velocity.0 * delta;
+        transform.rotation *= Quat::from_axis_angle(Vec3::Z, angular_velocity.0 * delta);
         transform.scale -= Vec3::new(1.0, 1.0, 0.0);
     }
 }
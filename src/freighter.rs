@@ -0,0 +1,159 @@
+// Friendly cargo ship escorted by the spaceship, which it follows at a distance
+use bevy::{prelude::*, render::mesh::PrimitiveTopology, sprite::Mesh2dHandle};
+use iyes_loopless::prelude::*;
+
+use crate::{
+    collision::detection::{triangle::Triangle, Aabb, Collider, Topology},
+    component::{AngularVelocity, Health, Part, PhysicsMaterial, Velocity},
+    spaceship::{self, Spaceship},
+    tick,
+};
+
+const HEALTH: u32 = 40;
+const HALF_LENGTH: f32 = 50.0;
+const HALF_WIDTH: f32 = 20.0;
+const NOSE: f32 = 70.0;
+const COLOR: Color = Color::rgb(0.5, 0.5, 0.8);
+// Freighters start behind the spaceship, one after the other
+const SPACING: f32 = 160.0;
+
+const F1: Vec3 = Vec3 {
+    x: -HALF_LENGTH,
+    y: -HALF_WIDTH,
+    z: 0.0,
+};
+const F2: Vec3 = Vec3 {
+    x: HALF_LENGTH,
+    y: -HALF_WIDTH,
+    z: 0.0,
+};
+const F3: Vec3 = Vec3 {
+    x: HALF_LENGTH,
+    y: HALF_WIDTH,
+    z: 0.0,
+};
+const F4: Vec3 = Vec3 {
+    x: -HALF_LENGTH,
+    y: HALF_WIDTH,
+    z: 0.0,
+};
+const F5: Vec3 = Vec3 {
+    x: NOSE,
+    y: 0.0,
+    z: 0.0,
+};
+
+// The hull is a box with a nose, pointing towards x
+const TRIANGLES: [Triangle; 3] = [
+    Triangle(F1, F2, F3),
+    Triangle(F1, F3, F4),
+    Triangle(F2, F5, F3),
+];
+
+#[derive(Component)]
+pub struct Freighter;
+
+pub fn spawn(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+    count: usize,
+) {
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+    let v_pos: Vec<[f32; 3]> = TRIANGLES
+        .iter()
+        .flat_map(|triangle| triangle.to_array())
+        .map(|vertex| vertex.to_array())
+        .collect();
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, v_pos);
+    let mesh_handle = meshes.add(mesh);
+    let material = materials.add(COLOR.into());
+
+    for k in 0..count {
+        let translation = spaceship::TRANSLATION - Vec3::new((k + 1) as f32 * SPACING, 0.0, 0.0);
+        let freighter = commands
+            .spawn(Freighter)
+            .insert(Velocity(Vec3::ZERO))
+            .insert(AngularVelocity(0.0))
+            .insert(SpatialBundle {
+                transform: Transform::from_translation(translation),
+                ..Default::default()
+            })
+            .id();
+
+        let freighter_part = commands
+            .spawn((Freighter, Part))
+            .insert(Health(HEALTH))
+            .insert(PhysicsMaterial::HULL)
+            .insert(Collider {
+                aabb: Aabb {
+                    hw: NOSE,
+                    hh: HALF_WIDTH,
+                },
+                topology: Topology::Triangles {
+                    mesh_handle: Mesh2dHandle(mesh_handle.clone_weak()),
+                },
+            })
+            .insert(ColorMesh2dBundle {
+                mesh: mesh_handle.clone().into(),
+                material: material.clone(),
+                ..Default::default()
+            })
+            .id();
+
+        commands.entity(freighter).add_child(freighter_part);
+    }
+}
+
+// Freighters head for the spaceship until they get close enough, then let themselves drift
+pub fn movement(
+    mut query_freighter: Query<
+        (&mut AngularVelocity, &mut Transform, &mut Velocity),
+        (With<Freighter>, Without<Part>),
+    >,
+    query_spaceship: Query<&Transform, (With<Spaceship>, Without<Part>, Without<Freighter>)>,
+    fixed_timesteps: Res<FixedTimesteps>,
+) {
+    let delta = tick::delta(&fixed_timesteps);
+    let maybe_spaceship = query_spaceship.get_single().ok();
+
+    for (mut angular_velocity, mut f_transform, mut velocity) in &mut query_freighter {
+        const FOLLOW_DISTANCE: f32 = 250.0;
+        const SPEED: f32 = 350.0;
+        const ACCELERATION: f32 = 200.0;
+        let target_velocity = match maybe_spaceship {
+            Some(s_transform) => {
+                let offset = s_transform.translation - f_transform.translation;
+                if offset.length() > FOLLOW_DISTANCE {
+                    SPEED * offset.normalize()
+                } else {
+                    Vec3::ZERO
+                }
+            }
+            None => Vec3::ZERO,
+        };
+        let dv = (target_velocity - velocity.0).clamp_length_max(ACCELERATION * delta);
+        velocity.0 += dv;
+
+        // Turn towards the direction of travel
+        let looking_at = f_transform.rotation * Vec3::X;
+        if velocity.0.length() > 1.0 {
+            let should_rotate = Quat::from_rotation_arc_2d(
+                looking_at.truncate(),
+                velocity.0.truncate().normalize(),
+            );
+            const ROTATION_SPEED: f32 = 10.0;
+            angular_velocity.0 += if should_rotate.to_axis_angle().0.z > 0.0 {
+                ROTATION_SPEED
+            } else {
+                -ROTATION_SPEED
+            } * delta;
+        }
+
+        const ANGULAR_DRAG: f32 = 5.0;
+        angular_velocity.0 *= (-ANGULAR_DRAG * delta).exp();
+
+        f_transform.translation += velocity.0 * delta;
+        f_transform.rotation *= Quat::from_axis_angle(Vec3::Z, angular_velocity.0 * delta);
+    }
+}
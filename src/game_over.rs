@@ -2,9 +2,9 @@ use bevy::{app::AppExit, prelude::*, text::Text2dBounds};
 use iyes_loopless::prelude::*;
 
 use crate::{
-    boss::Boss,
-    component::{Health, Indestructible},
+    component::{Indestructible, Part},
     game_state::GameState,
+    mission::{Mission, Status},
     spaceship::Spaceship,
 };
 
@@ -20,13 +20,43 @@ const BOX_CENTER_LEFT: Vec3 = Vec3 {
     z: 0.0,
 };
 
+// Ends the game once the mission is over, either way.
+// The spaceship is out of harm's way from then on if the mission was cleared.
+pub fn end(
+    mut commands: Commands,
+    current_state: Res<CurrentState<GameState>>,
+    mission: Res<Mission>,
+    query_spaceship: Query<Entity, (With<Spaceship>, With<Part>)>,
+) {
+    if current_state.0 == GameState::GameOver || mission.status == Status::InProgress {
+        return;
+    }
+
+    commands.insert_resource(NextState(GameState::GameOver));
+    if mission.status == Status::Completed {
+        if let Ok(spaceship) = query_spaceship.get_single() {
+            commands.entity(spaceship).insert(Indestructible);
+        }
+    }
+}
+
+// Shown once the game is over. It is already there when coming back from the pause menu.
 pub fn spawn_text(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
-    query_boss: Query<&Health, With<Boss>>,
+    mission: Res<Mission>,
     query_camera: Query<&Transform, With<Camera>>,
-    query_spaceship: Query<(Entity, &Health), With<Spaceship>>,
+    query_game_over: Query<With<GameOver>>,
 ) {
+    if !query_game_over.is_empty() {
+        return;
+    }
+    let value = match mission.status {
+        Status::InProgress => return,
+        Status::Completed => "Mission cleared. Press Enter to go back to the main menu",
+        Status::Failed => "Mission failed. Press Enter to go back to the main menu",
+    };
+
     const FONT: &str = "fonts/FiraSans-Bold.ttf";
     const COLOR: Color = Color::rgb(0.0, 0.0, 0.0);
     let text_style = TextStyle {
@@ -36,43 +66,14 @@ pub fn spawn_text(
     };
     let text_alignment = TextAlignment::CENTER_LEFT;
 
-    if let Ok((spaceship, health)) = query_spaceship.get_single() {
-        if health.0 == 0 {
-            commands.spawn(GameOver).insert(Text2dBundle {
-                text: Text::from_section(
-                    "Mission failed. Press Enter to go back to the main menu",
-                    text_style,
-                )
-                .with_alignment(text_alignment),
-                text_2d_bounds: Text2dBounds {
-                    size: Vec2::new(BOX_WIDTH, BOX_HEIGHT),
-                },
-                transform: Transform::from_translation(
-                    query_camera.single().translation + BOX_CENTER_LEFT,
-                ),
-                ..Default::default()
-            });
-        } else if let Ok(health) = query_boss.get_single() {
-            if health.0 == 0 {
-                commands.spawn(GameOver).insert(Text2dBundle {
-                    text: Text::from_section(
-                        "Mission cleared. Press Enter to go back to the main menu",
-                        text_style,
-                    )
-                    .with_alignment(text_alignment),
-                    text_2d_bounds: Text2dBounds {
-                        size: Vec2::new(BOX_WIDTH, BOX_HEIGHT),
-                    },
-                    transform: Transform::from_translation(
-                        query_camera.single().translation + BOX_CENTER_LEFT,
-                    ),
-                    ..Default::default()
-                });
-
-                commands.entity(spaceship).insert(Indestructible);
-            }
-        }
-    }
+    commands.spawn(GameOver).insert(Text2dBundle {
+        text: Text::from_section(value, text_style).with_alignment(text_alignment),
+        text_2d_bounds: Text2dBounds {
+            size: Vec2::new(BOX_WIDTH, BOX_HEIGHT),
+        },
+        transform: Transform::from_translation(query_camera.single().translation + BOX_CENTER_LEFT),
+        ..Default::default()
+    });
 }
 
 pub fn update_text(
@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 use iyes_loopless::prelude::*;
 
-use crate::{component::Part, keyboard_bindings::KeyboardBindings, spaceship::Spaceship};
+use crate::{component::Part, intent::Intent, spaceship::Spaceship};
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum GameState {
@@ -19,19 +19,25 @@ pub fn gamesetup_to_turnuplight(mut commands: Commands) {
     commands.insert_resource(NextState(GameState::TurnUpLight));
 }
 
+pub fn gamesetup_to_ingame(mut commands: Commands) {
+    commands.insert_resource(NextState(GameState::InGame));
+}
+
 pub fn ingame_to_paused(
     mut commands: Commands,
-    input: Res<Input<KeyCode>>,
-    query_bindings: Query<&KeyboardBindings>,
+    intent: Res<Intent>,
     query_spaceship: Query<(With<Spaceship>, Without<Part>)>,
 ) {
-    if query_spaceship.get_single().is_ok()
-        && input.any_just_pressed([KeyCode::Escape, query_bindings.single().pause()])
-    {
+    if query_spaceship.get_single().is_ok() && intent.pause {
         commands.insert_resource(NextState(GameState::Paused));
     }
 }
 
-pub fn gamesetup_or_ingame(current_state: Res<CurrentState<GameState>>) -> bool {
-    current_state.0 == GameState::GameSetup || current_state.0 == GameState::InGame
+pub fn gamesetup_ingame_or_gameover(current_state: Res<CurrentState<GameState>>) -> bool {
+    current_state.0 == GameState::GameSetup || ingame_or_gameover(current_state)
+}
+
+// The world goes on once the mission is over
+pub fn ingame_or_gameover(current_state: Res<CurrentState<GameState>>) -> bool {
+    current_state.0 == GameState::InGame || current_state.0 == GameState::GameOver
 }
@@ -0,0 +1,71 @@
+use bevy::prelude::*;
+
+use crate::keyboard_bindings::KeyboardBindings;
+
+// What the player asks of the game during a tick, whether it comes from the keyboard or a replay.
+// Held keys are captured by accelerate, decelerate and the rotations, presses by the others.
+// Presses are kept until the end of the next tick so that none is lost between ticks.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Resource)]
+pub struct Intent {
+    pub accelerate: bool,
+    pub decelerate: bool,
+    pub rotate_left: bool,
+    pub rotate_right: bool,
+    pub fire: bool,
+    pub camera: bool,
+    pub pause: bool,
+}
+
+impl Intent {
+    pub fn to_bits(self) -> u8 {
+        [
+            self.accelerate,
+            self.decelerate,
+            self.rotate_left,
+            self.rotate_right,
+            self.fire,
+            self.camera,
+            self.pause,
+        ]
+        .into_iter()
+        .enumerate()
+        .fold(0, |bits, (i, bit)| bits | (bit as u8) << i)
+    }
+
+    pub fn from_bits(bits: u8) -> Intent {
+        let bit = |i: usize| bits & 1 << i != 0;
+        Intent {
+            accelerate: bit(0),
+            decelerate: bit(1),
+            rotate_left: bit(2),
+            rotate_right: bit(3),
+            fire: bit(4),
+            camera: bit(5),
+            pause: bit(6),
+        }
+    }
+}
+
+pub fn from_keyboard(
+    mut intent: ResMut<Intent>,
+    keys: Res<Input<KeyCode>>,
+    query_bindings: Query<&KeyboardBindings>,
+) {
+    if let Ok(bindings) = query_bindings.get_single() {
+        *intent = Intent {
+            accelerate: keys.any_pressed([bindings.accelerate(), KeyCode::Up]),
+            decelerate: keys.any_pressed([bindings.decelerate(), KeyCode::Down]),
+            rotate_left: keys.any_pressed([bindings.rotate_left(), KeyCode::Left]),
+            rotate_right: keys.any_pressed([bindings.rotate_right(), KeyCode::Right]),
+            fire: intent.fire || keys.just_pressed(bindings.fire()),
+            camera: intent.camera || keys.just_pressed(bindings.camera()),
+            pause: intent.pause || keys.any_just_pressed([KeyCode::Escape, bindings.pause()]),
+        };
+    }
+}
+
+pub fn consume(mut intent: ResMut<Intent>) {
+    intent.fire = false;
+    intent.camera = false;
+    intent.pause = false;
+}
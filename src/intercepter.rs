@@ -1,26 +1,26 @@
 use bevy::{prelude::*, render::mesh::PrimitiveTopology, sprite::Mesh2dHandle};
+use iyes_loopless::prelude::*;
 use rand::Rng;
 use std::f32::consts::PI;
 
 use crate::{
     blast::BlastEvent,
     collision::detection::{triangle::Triangle, Aabb, Collider, Topology},
-    component::{AngularVelocity, Attack, Health, Mass, MomentOfInertia, Part, Velocity},
-    constant::{WINDOW_HEIGHT, WINDOW_WIDTH, WINDOW_Z},
+    component::{AngularVelocity, Attack, CriticalZone, Health, Part, PhysicsMaterial, Velocity},
+    constant::{ANGULAR_DRAG, DRAG, WINDOW_HEIGHT, WINDOW_WIDTH, WINDOW_Z},
+    explosion::Explosive,
     fire::{Fire, FireEvent},
+    rng::{GameRng, Stream},
     spaceship::Spaceship,
+    tick,
 };
 
 const SQRT_3: f32 = 1.732_050_8; // 1.73205080756887729352744634151
 const CORE_RADIUS: f32 = 12.0;
 const CORE_VERTICES: usize = 8;
-const CORE_AREA: f32 = PI * CORE_RADIUS * CORE_RADIUS;
 const WING_EDGE: f32 = 30.0;
 const WING_HEIGHT: f32 = WING_EDGE * SQRT_3 / 2.0;
-const WING_AREA: f32 = WING_EDGE * WING_HEIGHT / 2.0;
-const AREA: f32 = CORE_AREA + 2.0 * WING_AREA;
-const MASS: f32 = AREA;
-const MOMENT_OF_INERTIA: f32 = 0.5 * MASS * AREA / PI;
+const HEALTH: u32 = 3;
 
 const A1: Vec3 = Vec3::ZERO;
 const A2: Vec3 = Vec3 {
@@ -54,7 +54,7 @@ pub struct Intercepter;
 #[derive(Component)]
 pub struct IntercepterPart;
 
-#[derive(Clone, Component, Copy, Eq, PartialEq)]
+#[derive(Clone, Component, Copy, Debug, Eq, PartialEq)]
 pub enum Behavior {
     Random,
     Chase,
@@ -64,9 +64,11 @@ pub fn spawn(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
+    mut stream: Local<Stream>,
+    game_rng: Res<GameRng>,
     query_camera: Query<&Transform, With<Camera>>,
 ) {
-    let mut rng = rand::thread_rng();
+    let rng = stream.get(&game_rng, "intercepter::spawn");
     let Vec3 { x: xc, y: yc, z: _ } = query_camera.single().translation;
     if rng.gen_range(0..10) != 0 {
         return;
@@ -78,17 +80,38 @@ pub fn spawn(
         yc + 2.0 * WINDOW_WIDTH * phi.sin(),
         WINDOW_Z,
     );
+
+    spawn_one(
+        &mut commands,
+        &mut meshes,
+        &mut materials,
+        Health(HEALTH),
+        Transform::from_translation(translation),
+        Velocity(Vec3::ZERO),
+        AngularVelocity(0.0),
+        Behavior::Random,
+    );
+}
+
+pub fn spawn_one(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+    health: Health,
+    transform: Transform,
+    velocity: Velocity,
+    angular_velocity: AngularVelocity,
+    behavior: Behavior,
+) -> Entity {
     let intercepter = commands
         .spawn(Intercepter)
-        .insert(Mass(MASS))
-        .insert(MomentOfInertia(MOMENT_OF_INERTIA))
-        .insert(Velocity(Vec3::ZERO))
-        .insert(AngularVelocity(0.0))
+        .insert(velocity)
+        .insert(angular_velocity)
         .insert(SpatialBundle {
-            transform: Transform::from_translation(translation),
+            transform,
             ..Default::default()
         })
-        .insert(Behavior::Random)
+        .insert(behavior)
         .id();
 
     let mut positions = Vec::with_capacity(CORE_VERTICES * 3 + 2 * 3);
@@ -130,13 +153,24 @@ pub fn spawn(
     mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
     // mesh.set_indices(Some(Indices::U32(indices)));
     let mesh_handle = meshes.add(mesh);
-    const HEALTH: u32 = 3;
     const COLOR: Color = Color::rgb(0.25, 1.0, 0.25);
     const ATTACK: Vec3 = Vec3::new(0.0, CORE_RADIUS, 0.0);
 
     let intercepter_part = commands
         .spawn((Intercepter, Part))
-        .insert(Health(HEALTH))
+        .insert(health)
+        .insert(PhysicsMaterial::HULL)
+        // Hits on the core rather than on the wings are deadlier
+        .insert(CriticalZone {
+            center: Vec2::ZERO,
+            radius: CORE_RADIUS,
+            multiplier: 2.0,
+        })
+        .insert(Explosive {
+            radius: 150.0,
+            damages: 10.0,
+            impulse: 300000.0,
+        })
         .insert(Collider {
             aabb: Aabb {
                 hw: WING_HEIGHT,
@@ -155,37 +189,31 @@ pub fn spawn(
         .id();
 
     commands.entity(intercepter).add_child(intercepter_part);
+
+    intercepter
 }
 
 pub fn movement(
-    mut commands: Commands,
     mut query_intercepter: Query<
         (
             &mut AngularVelocity,
             &mut Behavior,
-            Entity,
             &mut Transform,
             &mut Velocity,
         ),
         With<Intercepter>,
     >,
+    mut stream: Local<Stream>,
+    game_rng: Res<GameRng>,
     query_spaceship: Query<&Transform, (With<Spaceship>, Without<Part>, Without<Intercepter>)>,
-    query_camera: Query<&Transform, (With<Camera>, Without<Intercepter>)>,
-    time: Res<Time>,
+    fixed_timesteps: Res<FixedTimesteps>,
 ) {
-    let mut rng = rand::thread_rng();
+    let delta = tick::delta(&fixed_timesteps);
+    let rng = stream.get(&game_rng, "intercepter::movement");
 
-    for (mut angular_velocity, mut behavior, id, mut i_transform, mut velocity) in
+    for (mut angular_velocity, mut behavior, mut i_transform, mut velocity) in
         query_intercepter.iter_mut()
     {
-        if (query_camera.single().translation - i_transform.translation)
-            .truncate()
-            .length()
-            > 2.5 * WINDOW_WIDTH
-        {
-            commands.entity(id).despawn_recursive();
-        }
-
         if *behavior == Behavior::Random {
             if let Ok(s_transform) = query_spaceship.get_single() {
                 const CHASE: f32 = WINDOW_HEIGHT / 2.0;
@@ -197,7 +225,7 @@ pub fn movement(
 
         let looking_at = i_transform.rotation * Quat::from_axis_angle(Vec3::Z, PI / 2.0) * Vec3::X;
         const ACCELERATION: f32 = 400.0;
-        velocity.0 += ACCELERATION * time.delta_seconds() * looking_at;
+        velocity.0 += ACCELERATION * delta * looking_at;
 
         let should_look_at = if *behavior == Behavior::Random || query_spaceship.is_empty() {
             Quat::from_rotation_z(rng.gen_range(-PI / 2.0..PI / 2.0)) * looking_at
@@ -212,30 +240,29 @@ pub fn movement(
             ROTATION_SPEED
         } else {
             -ROTATION_SPEED
-        } * time.delta_seconds();
+        } * delta;
 
-        const DRAG: f32 = 0.05;
-        velocity.0 *= 1.0 - DRAG;
-        const ANGULAR_DRAG: f32 = 0.25;
-        angular_velocity.0 *= 1.0 - ANGULAR_DRAG;
+        velocity.0 *= (-DRAG * delta).exp();
+        angular_velocity.0 *= (-ANGULAR_DRAG * delta).exp();
 
-        i_transform.translation += velocity.0 * time.delta_seconds();
-        i_transform.rotation *=
-            Quat::from_axis_angle(Vec3::Z, angular_velocity.0 * time.delta_seconds());
+        i_transform.translation += velocity.0 * delta;
+        i_transform.rotation *= Quat::from_axis_angle(Vec3::Z, angular_velocity.0 * delta);
     }
 }
 
 pub fn attack(
     mut blast_event: EventWriter<BlastEvent>,
     mut fire_event: EventWriter<FireEvent>,
+    mut stream: Local<Stream>,
+    game_rng: Res<GameRng>,
     query_intercepter: Query<&Transform, (With<Intercepter>, Without<Part>)>,
     query_intercepter_part: Query<(&Attack, &Parent, &Transform), (With<Intercepter>, With<Part>)>,
     query_spaceship: Query<&Transform, (With<Spaceship>, Without<Part>)>,
 ) {
+    let rng = stream.get(&game_rng, "intercepter::attack");
     if let Ok(s_transform) = query_spaceship.get_single() {
         for (ip_attack, ip_parent, ip_transform) in query_intercepter_part.iter() {
             let i_transform = query_intercepter.get(**ip_parent).unwrap();
-            let mut rng = rand::thread_rng();
             const ATTACK_RATE: usize = 20;
             if rng.gen_range(0..ATTACK_RATE) == 0 {
                 let attack_absolute_translation =
@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 
-pub const BINDINGS: usize = 7;
+pub const BINDINGS: usize = 9;
 
 #[derive(Component)]
 pub struct KeyboardBindings(pub [KeyCode; BINDINGS]);
@@ -15,6 +15,8 @@ impl Default for KeyboardBindings {
             KeyCode::R,
             KeyCode::Space,
             KeyCode::P,
+            KeyCode::T,
+            KeyCode::B,
         ])
     }
 }
@@ -51,6 +53,14 @@ impl KeyboardBindings {
     pub fn pause(&self) -> KeyCode {
         self.0[6]
     }
+
+    pub fn next_target(&self) -> KeyCode {
+        self.0[7]
+    }
+
+    pub fn place_marker(&self) -> KeyCode {
+        self.0[8]
+    }
 }
 
 pub const KEYCODESTRING: [&str; 163] = [
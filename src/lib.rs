@@ -5,15 +5,32 @@ pub use crate::{
     blast::{Blast, BlastEvent},
     boss::Boss,
     collision::{
-        detection::{self, Aabb, Collider, Topology},
+        cache::{Cache, Collision, ContactEvent, Phase},
+        damages::DamageEvent,
+        detection::{self, Aabb, Collider, Contact, Topology},
+        generic::CollisionEvent,
         impact::{self, Impact, ImpactEvent},
     },
     component::*,
-    constant::{WINDOW_HEIGHT, WINDOW_WIDTH, WINDOW_Z},
+    constant::{TIMESTEP, WINDOW_HEIGHT, WINDOW_WIDTH, WINDOW_Z},
+    explosion::{ExplosionEvent, Explosive},
     fire::{Fire, FireEvent},
+    freighter::Freighter,
     game_state::GameState,
+    intent::Intent,
     intercepter::Intercepter,
-    map::star::{self, StarsEvent},
+    map::{
+        dormant::{self, RestoreEvent},
+        star::{self, StarsEvent},
+        WorldSeed,
+    },
+    mission::{Goal, Mission},
+    plugin::{
+        AsteroidsSimulationPlugin, AsteroidsUiPlugin, HeadlessPlugins, RecordPlugin, ReplayPlugin,
+    },
+    replay::Recording,
+    rng::GameRng,
+    shared_assets::SharedAssets,
     spaceship::Spaceship,
     wreckage::Wreckage,
 };
@@ -27,16 +44,27 @@ pub mod compass;
 pub mod component;
 pub mod constant;
 pub mod despawn;
+pub mod explosion;
 pub mod fire;
+pub mod freighter;
 pub mod game_over;
 pub mod game_state;
 pub mod health_bar;
+pub mod intent;
 pub mod intercepter;
 pub mod keyboard_bindings;
 pub mod light;
 pub mod map;
+pub mod mission;
 pub mod objective;
+pub mod plugin;
+pub mod radar;
+pub mod replay;
+pub mod rng;
+pub mod shared_assets;
 pub mod spaceship;
+pub mod tick;
 pub mod transform;
 pub mod ui;
+pub mod waypoint;
 pub mod wreckage;
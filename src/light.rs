@@ -1,4 +1,4 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, utils::HashSet};
 use iyes_loopless::prelude::*;
 
 use crate::{
@@ -10,22 +10,33 @@ use crate::{
 const DIM_FACTOR: f32 = 0.92;
 const DIM_TIMER: u32 = 50;
 
+// Materials of the visible meshes, each one once however many meshes share it
+fn visible_materials<'a>(
+    query_visible_mesh: impl IntoIterator<Item = (&'a Handle<ColorMaterial>, &'a ComputedVisibility)>,
+) -> Vec<&'a Handle<ColorMaterial>> {
+    let mut ids = HashSet::new();
+    query_visible_mesh
+        .into_iter()
+        .filter(|(color_material, visibility)| {
+            visibility.is_visible() && ids.insert(color_material.id())
+        })
+        .map(|(color_material, _)| color_material)
+        .collect()
+}
+
 pub fn turn_down(
     mut commands: Commands,
     mut materials: ResMut<Assets<ColorMaterial>>,
     mut query_camera: Query<(&mut Camera, &mut UiCameraConfig)>,
-    mut query_visible_mesh: Query<(&Handle<ColorMaterial>, &ComputedVisibility)>,
+    query_visible_mesh: Query<(&Handle<ColorMaterial>, &ComputedVisibility)>,
     mut query_visible_text: Query<(&ComputedVisibility, &mut Text)>,
     mut timer: Local<u32>,
     query_main_menu: Query<Entity, With<MainMenu>>,
     query_reset: Query<Entity, (Without<KeyboardBindings>, Without<Camera>)>,
     query_settings_menu: Query<Entity, With<SettingsMenu>>,
 ) {
-    for (color_material, visibility) in &mut query_visible_mesh {
-        if visibility.is_visible() {
-            materials.get_mut(color_material).unwrap().color *=
-                [DIM_FACTOR, DIM_FACTOR, DIM_FACTOR];
-        }
+    for color_material in visible_materials(&query_visible_mesh) {
+        materials.get_mut(color_material).unwrap().color *= [DIM_FACTOR, DIM_FACTOR, DIM_FACTOR];
     }
 
     for (visibility, mut text) in &mut query_visible_text {
@@ -59,15 +70,13 @@ pub fn turn_down(
 pub fn turn_up(
     mut commands: Commands,
     mut materials: ResMut<Assets<ColorMaterial>>,
-    mut query_visible_mesh: Query<(&Handle<ColorMaterial>, &ComputedVisibility)>,
+    query_visible_mesh: Query<(&Handle<ColorMaterial>, &ComputedVisibility)>,
     mut query_visible_text: Query<(&ComputedVisibility, &mut Text)>,
     mut timer: Local<u32>,
 ) {
-    for (color_material, visibility) in &mut query_visible_mesh {
-        if visibility.is_visible() {
-            materials.get_mut(color_material).unwrap().color *=
-                [1.0 / DIM_FACTOR, 1.0 / DIM_FACTOR, 1.0 / DIM_FACTOR];
-        }
+    for color_material in visible_materials(&query_visible_mesh) {
+        materials.get_mut(color_material).unwrap().color *=
+            [1.0 / DIM_FACTOR, 1.0 / DIM_FACTOR, 1.0 / DIM_FACTOR];
     }
 
     for (visibility, mut text) in &mut query_visible_text {
@@ -85,16 +94,13 @@ pub fn turn_up(
 
 pub fn kill(
     mut materials: ResMut<Assets<ColorMaterial>>,
-    mut query_visible_mesh: Query<(&Handle<ColorMaterial>, &ComputedVisibility)>,
+    query_visible_mesh: Query<(&Handle<ColorMaterial>, &ComputedVisibility)>,
     mut query_visible_text: Query<(&ComputedVisibility, &mut Text)>,
 ) {
     let kill_factor = DIM_FACTOR.powi(DIM_TIMER as i32);
 
-    for (color_material, visibility) in &mut query_visible_mesh {
-        if visibility.is_visible() {
-            materials.get_mut(color_material).unwrap().color *=
-                [kill_factor, kill_factor, kill_factor];
-        }
+    for color_material in visible_materials(&query_visible_mesh) {
+        materials.get_mut(color_material).unwrap().color *= [kill_factor, kill_factor, kill_factor];
     }
 
     for (visibility, mut text) in &mut query_visible_text {
@@ -0,0 +1,274 @@
+// Bodies of the sectors out of range.
+//
+// Asteroids, intercepters and wreckages drifting into a sector whose visibility is off
+// are taken out of the world and kept by the sector as they were,
+// to be spawned back when the sector comes into range again.
+// Time stands still in there: they resume their course where they left it.
+//
+// Only bodies of a single part, as asteroids and intercepters are spawned, can be kept so.
+// The spaceship, the boss and freighters are never made dormant nor lost:
+// the camera follows the first and the others are objectives,
+// which keep running wherever they are.
+use bevy::{prelude::*, sprite::Mesh2dHandle, utils::HashMap};
+
+use crate::{
+    asteroid::{self, Asteroid},
+    collision::detection::Collider,
+    component::{AngularVelocity, Health, Part, Velocity},
+    constant::WINDOW_WIDTH,
+    intercepter::{self, Behavior, Intercepter},
+    transform,
+    wreckage::{Wreckage, WreckageDebris},
+};
+
+use super::{sector_of, Sector};
+
+// Transforms are global, the ones of the parts of the bodies being folded into their own
+#[derive(Clone, Debug)]
+pub enum Dormant {
+    Asteroid {
+        outline: Vec<Vec2>,
+        health: Health,
+        transform: Transform,
+        velocity: Velocity,
+        angular_velocity: AngularVelocity,
+    },
+    Intercepter {
+        health: Health,
+        transform: Transform,
+        velocity: Velocity,
+        angular_velocity: AngularVelocity,
+        behavior: Behavior,
+    },
+    Wreckage {
+        health: Health,
+        transform: Transform,
+        velocity: Velocity,
+        debris: Vec<DormantDebris>,
+    },
+}
+
+// Debris keep their meshes and materials, drawn at random when the wreckage appeared
+#[derive(Clone, Debug)]
+pub struct DormantDebris {
+    mesh: Handle<Mesh>,
+    material: Handle<ColorMaterial>,
+    transform: Transform,
+    velocity: Velocity,
+}
+
+pub struct RestoreEvent {
+    pub bodies: Vec<Dormant>,
+}
+
+// Bodies already dying are left to be wrecked.
+// Those drifting off the map, where no sector was ever created, are lost
+// as soon as they are far enough.
+pub fn store(
+    mut commands: Commands,
+    mut query_sector: Query<(Entity, &mut Sector, &Visibility)>,
+    query_asteroid: Query<
+        (&AngularVelocity, &Children, Entity, &Transform, &Velocity),
+        (With<Asteroid>, Without<Part>),
+    >,
+    query_asteroid_part: Query<(&Collider, &Health, &Transform), (With<Asteroid>, With<Part>)>,
+    query_intercepter: Query<
+        (
+            &AngularVelocity,
+            &Behavior,
+            &Children,
+            Entity,
+            &Transform,
+            &Velocity,
+        ),
+        (With<Intercepter>, Without<Part>),
+    >,
+    query_intercepter_part: Query<(&Health, &Transform), (With<Intercepter>, With<Part>)>,
+    query_wreckage: Query<(&Children, Entity, &Health, &Transform, &Velocity), With<Wreckage>>,
+    query_debris: Query<
+        (&Handle<ColorMaterial>, &Mesh2dHandle, &Transform, &Velocity),
+        With<WreckageDebris>,
+    >,
+    query_camera: Query<&Transform, With<Camera>>,
+    meshes: Res<Assets<Mesh>>,
+) {
+    let camera = query_camera.single().translation;
+    let sectors: HashMap<(isize, isize), (Entity, bool)> = query_sector
+        .iter()
+        .map(|(sector_id, sector, visibility)| {
+            ((sector.i, sector.j), (sector_id, visibility.is_visible))
+        })
+        .collect();
+
+    let mut dormant: Vec<(Entity, Entity, Dormant)> = Vec::new();
+    let mut lost: Vec<Entity> = Vec::new();
+    let mut sort = |body: Entity, translation: Vec3, make: &dyn Fn() -> Dormant| match sectors
+        .get(&sector_of(translation))
+    {
+        Some((_, true)) => {}
+        Some((sector_id, false)) => dormant.push((*sector_id, body, make())),
+        None => {
+            if (camera - translation).truncate().length() > 2.5 * WINDOW_WIDTH {
+                lost.push(body);
+            }
+        }
+    };
+
+    for (angular_velocity, children, asteroid, transform, velocity) in &query_asteroid {
+        let parts: Vec<_> = children
+            .iter()
+            .filter_map(|child| query_asteroid_part.get(*child).ok())
+            .collect();
+        // Asteroids are made of a single part
+        if let [(collider, health, part_transform)] = parts[..] {
+            if health.0 == 0 {
+                continue;
+            }
+            let global_transform = transform::global_of(*part_transform, *transform);
+            sort(asteroid, global_transform.translation, &|| {
+                Dormant::Asteroid {
+                    outline: asteroid::outline_of(collider, &meshes),
+                    health: *health,
+                    transform: global_transform,
+                    velocity: *velocity,
+                    angular_velocity: *angular_velocity,
+                }
+            });
+        }
+    }
+
+    for (angular_velocity, behavior, children, intercepter, transform, velocity) in
+        &query_intercepter
+    {
+        let parts: Vec<_> = children
+            .iter()
+            .filter_map(|child| query_intercepter_part.get(*child).ok())
+            .collect();
+        // Intercepters are made of a single part
+        if let [(health, part_transform)] = parts[..] {
+            if health.0 == 0 {
+                continue;
+            }
+            let global_transform = transform::global_of(*part_transform, *transform);
+            sort(intercepter, global_transform.translation, &|| {
+                Dormant::Intercepter {
+                    health: *health,
+                    transform: global_transform,
+                    velocity: *velocity,
+                    angular_velocity: *angular_velocity,
+                    behavior: *behavior,
+                }
+            });
+        }
+    }
+
+    for (children, wreckage, health, transform, velocity) in &query_wreckage {
+        if health.0 == 0 {
+            continue;
+        }
+        sort(wreckage, transform.translation, &|| Dormant::Wreckage {
+            health: *health,
+            transform: *transform,
+            velocity: *velocity,
+            debris: children
+                .iter()
+                .filter_map(|child| query_debris.get(*child).ok())
+                .map(|(material, mesh, transform, velocity)| DormantDebris {
+                    mesh: mesh.0.clone(),
+                    material: material.clone(),
+                    transform: *transform,
+                    velocity: *velocity,
+                })
+                .collect(),
+        });
+    }
+
+    for (sector_id, body, dormant) in dormant {
+        commands.entity(body).despawn_recursive();
+        query_sector
+            .get_component_mut::<Sector>(sector_id)
+            .unwrap()
+            .dormant
+            .push(dormant);
+    }
+    for body in lost {
+        commands.entity(body).despawn_recursive();
+    }
+}
+
+pub fn restore(
+    mut commands: Commands,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut restore_event: EventReader<RestoreEvent>,
+) {
+    for ev in restore_event.iter() {
+        for dormant in ev.bodies.iter().cloned() {
+            match dormant {
+                Dormant::Asteroid {
+                    outline,
+                    health,
+                    transform,
+                    velocity,
+                    angular_velocity,
+                } => {
+                    asteroid::spawn_one(
+                        &mut commands,
+                        &mut materials,
+                        &mut meshes,
+                        outline,
+                        health,
+                        transform,
+                        velocity,
+                        angular_velocity,
+                    );
+                }
+                Dormant::Intercepter {
+                    health,
+                    transform,
+                    velocity,
+                    angular_velocity,
+                    behavior,
+                } => {
+                    intercepter::spawn_one(
+                        &mut commands,
+                        &mut meshes,
+                        &mut materials,
+                        health,
+                        transform,
+                        velocity,
+                        angular_velocity,
+                        behavior,
+                    );
+                }
+                Dormant::Wreckage {
+                    health,
+                    transform,
+                    velocity,
+                    debris,
+                } => {
+                    commands
+                        .spawn(Wreckage)
+                        .insert(health)
+                        .insert(velocity)
+                        .insert(SpatialBundle {
+                            transform,
+                            ..Default::default()
+                        })
+                        .with_children(|parent| {
+                            for debris in debris {
+                                parent.spawn(WreckageDebris).insert(debris.velocity).insert(
+                                    ColorMesh2dBundle {
+                                        mesh: debris.mesh.into(),
+                                        transform: debris.transform,
+                                        material: debris.material,
+                                        ..default()
+                                    },
+                                );
+                            }
+                        });
+                }
+            }
+        }
+    }
+}
@@ -1,12 +1,56 @@
 use bevy::prelude::*;
 use rand::Rng;
+use rand_pcg::Pcg32;
+use std::f32::consts::PI;
 
-use crate::constant::{WINDOW_HEIGHT, WINDOW_WIDTH};
+use crate::{
+    constant::{WINDOW_HEIGHT, WINDOW_WIDTH},
+    rng::{self, GameRng},
+};
+use dormant::{Dormant, RestoreEvent};
 use star::StarsEvent;
 
+pub mod dormant;
 pub mod star;
 
 const SECTOR_Z: f32 = 0.0;
+// Stream of the seed of a sector its features are drawn from, apart from its stars
+const FEATURES_STREAM: u64 = 1;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SectorKind {
+    Empty,
+    // Rocks drifting in every direction
+    Field,
+    // Crowd of smaller rocks all flowing the same way
+    Belt,
+}
+
+impl SectorKind {
+    // Mean number of asteroids appearing in a sector per second
+    pub fn density(&self) -> f32 {
+        match self {
+            SectorKind::Empty => 0.0,
+            SectorKind::Field => 0.75,
+            SectorKind::Belt => 3.0,
+        }
+    }
+}
+
+// Coordinates of the sector containing the point
+pub fn sector_of(translation: Vec3) -> (isize, isize) {
+    (
+        (translation.x / WINDOW_WIDTH).floor() as isize,
+        (translation.y / WINDOW_HEIGHT).floor() as isize,
+    )
+}
+
+pub fn center_of(i: isize, j: isize) -> Vec2 {
+    Vec2::new(
+        (i as f32 + 0.5) * WINDOW_WIDTH,
+        (j as f32 + 0.5) * WINDOW_HEIGHT,
+    )
+}
 
 #[derive(Clone, Component, Debug)]
 pub struct Sector {
@@ -14,13 +58,61 @@ pub struct Sector {
     j: isize,
     neighboors: Vec<Entity>,
     seed: u64,
+    pub kind: SectorKind,
+    // Direction in which the asteroids of a belt flow
+    pub heading: f32,
+    // Bodies kept while the sector is out of range
+    dormant: Vec<Dormant>,
+}
+
+impl Sector {
+    // The features of a sector only depend on its seed
+    pub fn new(i: isize, j: isize, seed: u64) -> Sector {
+        let mut rng = Pcg32::new(seed, FEATURES_STREAM);
+        let kind = match rng.gen_range(0.0..1.0) {
+            x if x < 0.3 => SectorKind::Empty,
+            x if x < 0.8 => SectorKind::Field,
+            _ => SectorKind::Belt,
+        };
+
+        Sector {
+            i,
+            j,
+            neighboors: Vec::new(),
+            seed,
+            kind,
+            heading: rng.gen_range(0.0..2.0 * PI),
+            dormant: Vec::new(),
+        }
+    }
 }
 
 #[derive(Debug, Resource)]
 pub struct CurrentSectorId(Entity);
 
-pub fn spawn(mut stars_event: EventWriter<StarsEvent>, mut commands: Commands) {
-    let mut rng = rand::thread_rng();
+// Seed the whole map derives from: the sector at (i, j) is the same in every match
+// played with the same world seed, however it was reached.
+#[derive(Clone, Copy, Debug, Resource)]
+pub struct WorldSeed(pub u64);
+
+impl WorldSeed {
+    pub fn of_sector(&self, i: isize, j: isize) -> u64 {
+        let bytes: Vec<u8> = [self.0, i as u64, j as u64]
+            .iter()
+            .flat_map(|n| n.to_le_bytes())
+            .collect();
+
+        rng::hash(&bytes)
+    }
+}
+
+pub fn spawn(
+    mut stars_event: EventWriter<StarsEvent>,
+    mut commands: Commands,
+    game_rng: Res<GameRng>,
+) {
+    let world_seed = WorldSeed(game_rng.fork("map::spawn").gen());
+    commands.insert_resource(world_seed);
     let mut sectors: Vec<(Entity, Sector)> = Vec::with_capacity(9);
 
     for i in [-1, 0, 1] {
@@ -36,16 +128,8 @@ pub fn spawn(mut stars_event: EventWriter<StarsEvent>, mut commands: Commands) {
                 })
                 .id();
 
-            let seed = rng.gen::<u64>();
-            sectors.push((
-                sector_id,
-                Sector {
-                    i,
-                    j,
-                    neighboors: Vec::new(),
-                    seed,
-                },
-            ));
+            let seed = world_seed.of_sector(i, j);
+            sectors.push((sector_id, Sector::new(i, j, seed)));
 
             stars_event.send(StarsEvent { sector_id, seed });
         }
@@ -86,10 +170,11 @@ pub fn update(
     mut commands: Commands,
     mut current_sector_id: ResMut<CurrentSectorId>,
     mut query_sector: Query<(Entity, &mut Sector, &mut Visibility)>,
+    mut restore_event: EventWriter<RestoreEvent>,
     mut stars_event: EventWriter<StarsEvent>,
+    world_seed: Res<WorldSeed>,
     query_camera: Query<&Transform, With<Camera>>,
 ) {
-    let mut rng = rand::thread_rng();
     let camera_xyz = query_camera.single().translation;
 
     let camera_i = (camera_xyz.x / WINDOW_WIDTH).floor() as isize;
@@ -124,7 +209,7 @@ pub fn update(
     for di in [-1isize, 0, 1] {
         'outer: for dj in [-1isize, 0, 1] {
             // Check if that sector is already known
-            for (sector_id, sector, mut visibility) in &mut query_sector {
+            for (sector_id, mut sector, mut visibility) in &mut query_sector {
                 if [sector.i, sector.j] == [camera_i + di, camera_j + dj] {
                     if !visibility.is_visible {
                         visibility.is_visible = true;
@@ -132,6 +217,9 @@ pub fn update(
                             sector_id,
                             seed: sector.seed,
                         });
+                        restore_event.send(RestoreEvent {
+                            bodies: std::mem::take(&mut sector.dormant),
+                        });
                     }
                     continue 'outer;
                 }
@@ -149,17 +237,9 @@ pub fn update(
                 })
                 .id();
 
-            let seed = rng.gen::<u64>();
             let [i, j] = [camera_i + di, camera_j + dj];
-            new_sectors.push((
-                new_sector_id,
-                Sector {
-                    i,
-                    j,
-                    neighboors: Vec::new(),
-                    seed,
-                },
-            ));
+            let seed = world_seed.of_sector(i, j);
+            new_sectors.push((new_sector_id, Sector::new(i, j, seed)));
 
             // Populate this new sector with stars
             stars_event.send(StarsEvent {
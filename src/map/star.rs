@@ -1,52 +1,209 @@
+// Sky of the sectors: layers of stars at different depths and, here and there,
+// a nebula or a planet far behind them.
+//
+// Each layer of a sector is shifted towards the camera by a share of the distance between them,
+// so that it seems to move slower than the world when the camera moves:
+// the smaller its parallax, the farther it looks.
+// Parallaxes stay above 2/3 for the layers of the sectors coming into or going out of range
+// to never be on screen.
 use bevy::prelude::*;
 use rand::{Rng, SeedableRng};
 use rand_pcg::Pcg32;
+use std::{f32::consts::PI, ops::Range};
 
-use crate::constant::{WINDOW_HEIGHT, WINDOW_WIDTH};
+use super::Sector;
+use crate::{
+    constant::{WINDOW_HEIGHT, WINDOW_WIDTH},
+    shared_assets::SharedAssets,
+};
 
 const BACKGROUND: f32 = 0.0;
-const RADIUS: f32 = 1.0;
 const VERTICES: usize = 4;
-const COLOR: Color = Color::WHITE;
-pub const STARS_PER_SECTOR: usize = 50;
+
+struct Layer {
+    parallax: f32,
+    stars: usize,
+    radius: Range<f32>,
+    colors: &'static [Color],
+}
+
+// From the farthest to the nearest
+const LAYERS: [Layer; 3] = [
+    Layer {
+        parallax: 0.7,
+        stars: 25,
+        radius: 0.5..1.0,
+        colors: &[
+            Color::rgb(0.6, 0.6, 0.8),
+            Color::rgb(0.5, 0.5, 0.6),
+            Color::rgb(0.7, 0.6, 0.6),
+        ],
+    },
+    Layer {
+        parallax: 0.85,
+        stars: 15,
+        radius: 0.8..1.4,
+        colors: &[
+            Color::rgb(0.8, 0.8, 1.0),
+            Color::rgb(0.9, 0.9, 0.9),
+            Color::rgb(1.0, 0.9, 0.7),
+        ],
+    },
+    Layer {
+        parallax: 1.0,
+        stars: 10,
+        radius: 1.2..2.0,
+        colors: &[Color::WHITE, Color::rgb(1.0, 0.95, 0.8)],
+    },
+];
+pub const STARS_PER_SECTOR: usize = LAYERS[0].stars + LAYERS[1].stars + LAYERS[2].stars;
+
+// Backdrops lie on the farthest layer, behind its stars
+const BACKDROP_VERTICES: usize = 32;
+const NEBULA_PROBABILITY: f64 = 0.2;
+const NEBULA_CLOUDS: Range<usize> = 4..9;
+const NEBULA_RADIUS: Range<f32> = 60.0..160.0;
+const NEBULA_ALPHA: f32 = 0.08;
+const NEBULA_COLORS: [Color; 3] = [
+    Color::rgb(0.6, 0.2, 0.8),
+    Color::rgb(0.2, 0.4, 0.9),
+    Color::rgb(0.9, 0.3, 0.4),
+];
+const PLANET_PROBABILITY: f64 = 0.1;
+const PLANET_RADIUS: Range<f32> = 20.0..80.0;
+const PLANET_COLORS: [Color; 3] = [
+    Color::rgb(0.3, 0.25, 0.2),
+    Color::rgb(0.2, 0.25, 0.3),
+    Color::rgb(0.3, 0.3, 0.25),
+];
 
 #[derive(Component)]
 pub struct Star;
 
+#[derive(Component)]
+pub struct Backdrop;
+
+#[derive(Component)]
+pub struct StarLayer {
+    pub parallax: f32,
+}
+
 pub struct StarsEvent {
     pub sector_id: Entity,
     pub seed: u64,
 }
 
+// Point of the sector, at least margin away from its borders
+fn random_point(rng: &mut impl Rng, margin: f32) -> Vec2 {
+    Vec2::new(
+        rng.gen_range(-WINDOW_WIDTH / 2.0 + margin..WINDOW_WIDTH / 2.0 - margin),
+        rng.gen_range(-WINDOW_HEIGHT / 2.0 + margin..WINDOW_HEIGHT / 2.0 - margin),
+    )
+}
+
 pub fn spawn(
     mut commands: Commands,
     mut materials: ResMut<Assets<ColorMaterial>>,
     mut meshes: ResMut<Assets<Mesh>>,
+    mut shared_assets: ResMut<SharedAssets>,
     mut stars_event: EventReader<StarsEvent>,
 ) {
     for ev in stars_event.iter() {
         let mut rng = Pcg32::seed_from_u64(ev.seed);
-        for _ in 0..STARS_PER_SECTOR {
-            let star = commands
-                .spawn(Star)
-                .insert(ColorMesh2dBundle {
-                    mesh: meshes
-                        .add(Mesh::from(shape::Circle {
-                            radius: RADIUS,
-                            vertices: VERTICES,
-                        }))
-                        .into(),
-                    transform: Transform::from_xyz(
-                        rng.gen_range(-WINDOW_WIDTH / 2.0..WINDOW_WIDTH / 2.0),
-                        rng.gen_range(-WINDOW_HEIGHT / 2.0..WINDOW_HEIGHT / 2.0),
-                        BACKGROUND,
-                    ),
-                    material: materials.add(COLOR.into()),
+        for (depth, layer) in LAYERS.iter().enumerate() {
+            let layer_id = commands
+                .spawn(StarLayer {
+                    parallax: layer.parallax,
+                })
+                .insert(SpatialBundle {
+                    transform: Transform::from_xyz(0.0, 0.0, BACKGROUND + 0.1 * depth as f32),
                     ..default()
                 })
                 .id();
+            commands.entity(ev.sector_id).add_child(layer_id);
 
-            commands.entity(ev.sector_id).add_child(star);
+            for _ in 0..layer.stars {
+                let color = layer.colors[rng.gen_range(0..layer.colors.len())];
+                let star = commands
+                    .spawn(Star)
+                    .insert(ColorMesh2dBundle {
+                        mesh: shared_assets
+                            .circle(&mut meshes, rng.gen_range(layer.radius.clone()), VERTICES)
+                            .into(),
+                        transform: Transform::from_translation(
+                            random_point(&mut rng, 0.0).extend(0.05),
+                        ),
+                        material: shared_assets.material(&mut materials, color),
+                        ..default()
+                    })
+                    .id();
+
+                commands.entity(layer_id).add_child(star);
+            }
+
+            if depth > 0 {
+                continue;
+            }
+
+            if rng.gen_bool(NEBULA_PROBABILITY) {
+                let color = NEBULA_COLORS[rng.gen_range(0..NEBULA_COLORS.len())];
+                let center = random_point(&mut rng, 2.0 * NEBULA_RADIUS.end);
+                for _ in 0..rng.gen_range(NEBULA_CLOUDS) {
+                    let radius = rng.gen_range(NEBULA_RADIUS);
+                    let rho = rng.gen_range(0.0..NEBULA_RADIUS.end);
+                    let theta = rng.gen_range(0.0..2.0 * PI);
+                    let cloud = commands
+                        .spawn(Backdrop)
+                        .insert(ColorMesh2dBundle {
+                            mesh: shared_assets
+                                .circle(&mut meshes, radius, BACKDROP_VERTICES)
+                                .into(),
+                            transform: Transform::from_translation(
+                                (center + rho * Vec2::new(theta.cos(), theta.sin())).extend(0.0),
+                            ),
+                            material: shared_assets
+                                .material(&mut materials, *color.clone().set_a(NEBULA_ALPHA)),
+                            ..default()
+                        })
+                        .id();
+
+                    commands.entity(layer_id).add_child(cloud);
+                }
+            }
+
+            if rng.gen_bool(PLANET_PROBABILITY) {
+                let radius = rng.gen_range(PLANET_RADIUS);
+                let color = PLANET_COLORS[rng.gen_range(0..PLANET_COLORS.len())];
+                let planet = commands
+                    .spawn(Backdrop)
+                    .insert(ColorMesh2dBundle {
+                        mesh: shared_assets
+                            .circle(&mut meshes, radius, BACKDROP_VERTICES)
+                            .into(),
+                        transform: Transform::from_translation(
+                            random_point(&mut rng, radius).extend(0.01),
+                        ),
+                        material: shared_assets.material(&mut materials, color),
+                        ..default()
+                    })
+                    .id();
+
+                commands.entity(layer_id).add_child(planet);
+            }
+        }
+    }
+}
+
+pub fn parallax(
+    mut query_layer: Query<(&StarLayer, &Parent, &mut Transform)>,
+    query_sector: Query<&Transform, (With<Sector>, Without<StarLayer>)>,
+    query_camera: Query<&Transform, (With<Camera>, Without<StarLayer>)>,
+) {
+    let camera = query_camera.single().translation.truncate();
+    for (layer, parent, mut transform) in &mut query_layer {
+        if let Ok(sector_transform) = query_sector.get(**parent) {
+            let shift = (1.0 - layer.parallax) * (camera - sector_transform.translation.truncate());
+            transform.translation = shift.extend(transform.translation.z);
         }
     }
 }
@@ -0,0 +1,342 @@
+// Mission of a match: a list of objectives, all of which must be completed for it to be cleared.
+//
+// Objectives are evaluated every tick from what happened during it and stay completed
+// once they are. The mission fails as soon as the spaceship is destroyed
+// or one of its objectives can no longer be completed.
+//
+// Missions are read from text files, one goal per line, blank lines and lines
+// starting with # being ignored:
+//     destroy_asteroids <asteroids>
+//     survive <seconds>
+//     reach_sector <i> <j>
+//     destroy_boss
+//     escort <freighters> <i> <j>
+use bevy::prelude::*;
+use iyes_loopless::prelude::*;
+use std::{fmt, fs, io, path::Path, str::FromStr};
+
+use crate::{
+    asteroid::Asteroid,
+    boss::BossCore,
+    component::{Health, Part},
+    freighter::{self, Freighter},
+    map,
+    spaceship::Spaceship,
+    tick,
+    waypoint::{Waypoint, WaypointKind},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Goal {
+    DestroyAsteroids(u32),
+    // In seconds
+    Survive(f32),
+    ReachSector(isize, isize),
+    DestroyBoss,
+    // Bring that many freighters to the sector, all of them in one piece
+    Escort {
+        freighters: usize,
+        i: isize,
+        j: isize,
+    },
+}
+
+impl Goal {
+    pub fn description(&self) -> String {
+        match *self {
+            Goal::DestroyAsteroids(asteroids) => format!("Destroy {} asteroids", asteroids),
+            Goal::Survive(seconds) => format!("Survive {:.0} seconds", seconds),
+            Goal::ReachSector(i, j) => format!("Reach sector ({}, {})", i, j),
+            Goal::DestroyBoss => "Eliminate the target".to_string(),
+            Goal::Escort { i, j, .. } => format!("Escort the freighters to sector ({}, {})", i, j),
+        }
+    }
+
+    // Sector the spaceship is headed for, if any
+    pub fn destination(&self) -> Option<(isize, isize)> {
+        match *self {
+            Goal::ReachSector(i, j) | Goal::Escort { i, j, .. } => Some((i, j)),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Goal {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Goal, String> {
+        fn arg<T: FromStr>(word: &str) -> Result<T, String> {
+            word.parse()
+                .map_err(|_| format!("invalid argument {}", word))
+        }
+
+        match s.split_whitespace().collect::<Vec<_>>()[..] {
+            ["destroy_asteroids", asteroids] => Ok(Goal::DestroyAsteroids(arg(asteroids)?)),
+            ["survive", seconds] => Ok(Goal::Survive(arg(seconds)?)),
+            ["reach_sector", i, j] => Ok(Goal::ReachSector(arg(i)?, arg(j)?)),
+            ["destroy_boss"] => Ok(Goal::DestroyBoss),
+            ["escort", freighters, i, j] => Ok(Goal::Escort {
+                freighters: arg(freighters)?,
+                i: arg(i)?,
+                j: arg(j)?,
+            }),
+            _ => Err(format!("unknown goal {}", s.trim())),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MissionError {
+    NoObjective,
+    // An escort without freighters would fail as soon as the game starts
+    EmptyEscort,
+    // Line number, counting from 1, and what is wrong with it
+    InvalidLine(usize, String),
+}
+
+impl fmt::Display for MissionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MissionError::NoObjective => write!(f, "mission without objective"),
+            MissionError::EmptyEscort => write!(f, "escort without freighters"),
+            MissionError::InvalidLine(line, error) => write!(f, "line {}: {}", line, error),
+        }
+    }
+}
+
+impl std::error::Error for MissionError {}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Status {
+    InProgress,
+    Completed,
+    Failed,
+}
+
+#[derive(Clone, Debug)]
+pub struct Objective {
+    pub goal: Goal,
+    pub status: Status,
+    pub asteroids_destroyed: u32,
+    pub time_survived: f32,
+}
+
+// What happened during a tick, as far as the mission is concerned
+#[derive(Clone, Debug, Default)]
+pub struct Report {
+    pub delta: f32,
+    pub asteroids_destroyed: u32,
+    // None once the spaceship is destroyed
+    pub spaceship_sector: Option<(isize, isize)>,
+    pub boss_destroyed: bool,
+    pub freighter_sectors: Vec<(isize, isize)>,
+    pub freighter_destroyed: bool,
+}
+
+#[derive(Clone, Debug, Resource)]
+pub struct Mission {
+    pub objectives: Vec<Objective>,
+    pub status: Status,
+}
+
+impl Mission {
+    pub fn new(goals: &[Goal]) -> Result<Mission, MissionError> {
+        if goals.is_empty() {
+            return Err(MissionError::NoObjective);
+        }
+        if goals
+            .iter()
+            .any(|goal| matches!(goal, Goal::Escort { freighters: 0, .. }))
+        {
+            return Err(MissionError::EmptyEscort);
+        }
+
+        Ok(Mission {
+            objectives: goals
+                .iter()
+                .map(|goal| Objective {
+                    goal: *goal,
+                    status: Status::InProgress,
+                    asteroids_destroyed: 0,
+                    time_survived: 0.0,
+                })
+                .collect(),
+            status: Status::InProgress,
+        })
+    }
+
+    pub fn parse(text: &str) -> Result<Mission, MissionError> {
+        let goals = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+            .map(|(index, line)| {
+                line.parse()
+                    .map_err(|error| MissionError::InvalidLine(index + 1, error))
+            })
+            .collect::<Result<Vec<Goal>, _>>()?;
+        Mission::new(&goals)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Mission> {
+        Mission::parse(&fs::read_to_string(path)?)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+
+    // Same objectives, none of them started
+    pub fn reset(&mut self) {
+        for objective in &mut self.objectives {
+            objective.status = Status::InProgress;
+            objective.asteroids_destroyed = 0;
+            objective.time_survived = 0.0;
+        }
+        self.status = Status::InProgress;
+    }
+
+    pub fn advance(&mut self, report: &Report) {
+        if self.status != Status::InProgress {
+            return;
+        }
+
+        for objective in &mut self.objectives {
+            if objective.status != Status::InProgress {
+                continue;
+            }
+            objective.status = match objective.goal {
+                Goal::DestroyAsteroids(asteroids) => {
+                    objective.asteroids_destroyed += report.asteroids_destroyed;
+                    if objective.asteroids_destroyed >= asteroids {
+                        Status::Completed
+                    } else {
+                        Status::InProgress
+                    }
+                }
+                Goal::Survive(seconds) => {
+                    objective.time_survived += report.delta;
+                    if objective.time_survived >= seconds {
+                        Status::Completed
+                    } else {
+                        Status::InProgress
+                    }
+                }
+                Goal::ReachSector(i, j) => {
+                    if report.spaceship_sector == Some((i, j)) {
+                        Status::Completed
+                    } else {
+                        Status::InProgress
+                    }
+                }
+                Goal::DestroyBoss => {
+                    if report.boss_destroyed {
+                        Status::Completed
+                    } else {
+                        Status::InProgress
+                    }
+                }
+                Goal::Escort { i, j, .. } => {
+                    if report.freighter_destroyed || report.freighter_sectors.is_empty() {
+                        Status::Failed
+                    } else if report.freighter_sectors.iter().all(|s| *s == (i, j)) {
+                        Status::Completed
+                    } else {
+                        Status::InProgress
+                    }
+                }
+            };
+        }
+
+        self.status = if report.spaceship_sector.is_none()
+            || self.objectives.iter().any(|o| o.status == Status::Failed)
+        {
+            Status::Failed
+        } else if self
+            .objectives
+            .iter()
+            .all(|o| o.status == Status::Completed)
+        {
+            Status::Completed
+        } else {
+            Status::InProgress
+        };
+    }
+}
+
+// The original mission
+impl Default for Mission {
+    fn default() -> Self {
+        Mission::new(&[Goal::DestroyBoss]).unwrap()
+    }
+}
+
+// Waypoint of the destination of an objective, removed once it is completed
+#[derive(Component)]
+pub struct Destination(usize);
+
+pub fn start(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut mission: ResMut<Mission>,
+) {
+    mission.reset();
+
+    for (index, objective) in mission.objectives.iter().enumerate() {
+        if let Goal::Escort { freighters, .. } = objective.goal {
+            freighter::spawn(&mut commands, &mut meshes, &mut materials, freighters);
+        }
+        if let Some((i, j)) = objective.goal.destination() {
+            commands
+                .spawn(Waypoint::new(
+                    WaypointKind::Objective,
+                    &format!("Sector ({}, {})", i, j),
+                ))
+                .insert(Destination(index))
+                .insert(TransformBundle::from_transform(
+                    Transform::from_translation(map::center_of(i, j).extend(0.0)),
+                ));
+        }
+    }
+}
+
+// Runs once the damages of the tick are dealt, before the dead parts are wrecked
+pub fn update(
+    mut commands: Commands,
+    mut mission: ResMut<Mission>,
+    query_asteroid_part: Query<&Health, (With<Asteroid>, With<Part>)>,
+    query_boss_core: Query<&Health, With<BossCore>>,
+    query_destination: Query<(Entity, &Destination)>,
+    query_freighter: Query<&Transform, (With<Freighter>, Without<Part>)>,
+    query_freighter_part: Query<&Health, (With<Freighter>, With<Part>)>,
+    query_spaceship: Query<&Transform, (With<Spaceship>, Without<Part>)>,
+    query_spaceship_part: Query<&Health, (With<Spaceship>, With<Part>)>,
+    fixed_timesteps: Res<FixedTimesteps>,
+) {
+    let spaceship_alive = query_spaceship_part
+        .get_single()
+        .is_ok_and(|health| health.0 > 0);
+    let report = Report {
+        delta: tick::delta(&fixed_timesteps),
+        asteroids_destroyed: query_asteroid_part
+            .iter()
+            .filter(|health| health.0 == 0)
+            .count() as u32,
+        spaceship_sector: query_spaceship
+            .get_single()
+            .ok()
+            .filter(|_| spaceship_alive)
+            .map(|transform| map::sector_of(transform.translation)),
+        boss_destroyed: query_boss_core.iter().any(|health| health.0 == 0),
+        freighter_sectors: query_freighter
+            .iter()
+            .map(|transform| map::sector_of(transform.translation))
+            .collect(),
+        freighter_destroyed: query_freighter_part.iter().any(|health| health.0 == 0),
+    };
+    mission.advance(&report);
+
+    for (entity, destination) in &query_destination {
+        if mission.objectives[destination.0].status != Status::InProgress {
+            commands.entity(entity).despawn();
+        }
+    }
+}
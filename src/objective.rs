@@ -1,9 +1,9 @@
 use bevy::{prelude::*, text::Text2dBounds};
 
-use crate::keyboard_bindings::KeyboardBindings;
+use crate::{keyboard_bindings::KeyboardBindings, mission::Mission};
 
 const FONT_SIZE: f32 = 24.0;
-const BOX_WIDTH: f32 = 360.0;
+const BOX_WIDTH: f32 = 480.0;
 const BOX_HEIGHT: f32 = FONT_SIZE;
 const BOX_CENTER_LEFT: Vec3 = Vec3 {
     x: -BOX_WIDTH / 2.0,
@@ -14,9 +14,22 @@ const BOX_CENTER_LEFT: Vec3 = Vec3 {
 #[derive(Clone, Component, Copy)]
 pub struct Objective;
 
+// The objectives of the mission, one per line below a heading when there are several
+pub fn text(mission: &Mission) -> String {
+    match mission.objectives.as_slice() {
+        [objective] => format!("Mission objective: {}", objective.goal.description()),
+        objectives => objectives
+            .iter()
+            .fold("Mission objectives:".to_string(), |text, objective| {
+                format!("{}\n{}", text, objective.goal.description())
+            }),
+    }
+}
+
 pub fn spawn_text(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    mission: Res<Mission>,
     query_camera: Query<&Transform, With<Camera>>,
 ) {
     const FONT: &str = "fonts/FiraSans-Bold.ttf";
@@ -27,12 +40,13 @@ pub fn spawn_text(
         color: COLOR,
     };
     let text_alignment = TextAlignment::CENTER_LEFT;
+    let value = text(&mission);
+    let lines = value.lines().count();
 
     commands.spawn(Objective).insert(Text2dBundle {
-        text: Text::from_section("Mission objective: Eliminate the target", text_style)
-            .with_alignment(text_alignment),
+        text: Text::from_section(value, text_style).with_alignment(text_alignment),
         text_2d_bounds: Text2dBounds {
-            size: Vec2::new(BOX_WIDTH, BOX_HEIGHT),
+            size: Vec2::new(BOX_WIDTH, BOX_HEIGHT * lines as f32),
         },
         transform: Transform::from_translation(query_camera.single().translation + BOX_CENTER_LEFT),
         ..Default::default()
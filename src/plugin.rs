@@ -0,0 +1,475 @@
+use bevy::{
+    app::PluginGroupBuilder, input::InputSystem, prelude::*, render::settings::WgpuSettings,
+    transform::TransformSystem, utils::Duration,
+};
+use iyes_loopless::{condition::ConditionalSystemDescriptor, prelude::*};
+use std::path::PathBuf;
+
+use crate::{
+    asteroid::{self, Asteroid, Fragmentation},
+    blast::{self, Blast, BlastEvent},
+    boss::{self, Boss},
+    camera,
+    collision::{
+        self,
+        cache::{Cache, ContactEvent},
+        damages::DamageEvent,
+        generic::CollisionEvent,
+        impact::{self, Impact, ImpactEvent},
+    },
+    compass::{self, ActiveWaypoint},
+    constant::TIMESTEP,
+    despawn,
+    explosion::{self, ExplosionEvent},
+    fire::{self, Fire, FireEvent},
+    freighter::{self, Freighter},
+    game_over,
+    game_state::{self, GameState},
+    health_bar,
+    intent::{self, Intent},
+    intercepter::{self, Intercepter},
+    keyboard_bindings, light,
+    map::{
+        self,
+        dormant::{self, RestoreEvent},
+        star::{self, StarsEvent},
+    },
+    mission::{self, Mission},
+    objective,
+    radar::{self, RadarRange},
+    replay::{self, Recorder, Recording, Replay},
+    rng::{self, GameRng},
+    shared_assets::{self, SharedAssets},
+    spaceship::{self, Spaceship},
+    tick::{self, Lockstep},
+    ui, waypoint,
+    wreckage::{self, Wreckage},
+};
+
+// Fixed timestep stage running the simulation before CoreStage::Update, once per tick.
+// Commands are applied at the end of each of its substages.
+pub const FIXED_UPDATE: &str = "fixed update";
+// Substages of FIXED_UPDATE, the last one in which dead entities are wrecked and despawned
+pub const PRE_UPDATE: usize = 0;
+pub const UPDATE: usize = 1;
+pub const CLEANUP: usize = 2;
+
+// Label of the system updating the mass of the bodies which gained or lost parts, in PRE_UPDATE
+pub const MASS: &str = "mass";
+
+// Label of the system interpolating the rendered transforms between ticks, in CoreStage::PostUpdate
+pub const INTERPOLATION: &str = "interpolation";
+
+// Label of the system reading the intent of the player from the keyboard, in CoreStage::PreUpdate
+pub const INTENT: &str = "intent";
+
+// Label of the system drawing the seed of a new game, before anything is spawned
+pub const SEED: &str = "seed";
+
+// Labels of the system sets running in states GameState::InGame and GameState::GameOver,
+// in FIXED_UPDATE
+pub const FREE: &str = "free";
+pub const MOVEMENT: &str = "movement";
+pub const COLLISION: &str = "collision";
+pub const IMPACT_EVENT: &str = "impact event";
+pub const EXPLOSION: &str = "explosion";
+pub const ATTACK: &str = "attack";
+pub const CAMERA: &str = "camera";
+
+/// The game itself: states, events and every system driving the world.
+///
+/// Matches are seeded from the `GameRng` resource, inserted beforehand to fix the seed.
+/// It needs neither a window nor a GPU and is meant to be paired with
+/// `AsteroidsUiPlugin` for the playable game or run within `HeadlessPlugins`.
+pub struct AsteroidsSimulationPlugin {
+    pub initial_state: GameState,
+}
+
+impl Default for AsteroidsSimulationPlugin {
+    fn default() -> Self {
+        AsteroidsSimulationPlugin {
+            initial_state: GameState::MainMenu,
+        }
+    }
+}
+
+impl Plugin for AsteroidsSimulationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_loopless_state(self.initial_state)
+            .add_fixed_timestep(Duration::from_secs_f32(TIMESTEP), FIXED_UPDATE)
+            .add_fixed_timestep_child_stage(FIXED_UPDATE)
+            .add_fixed_timestep_child_stage(FIXED_UPDATE)
+            .add_event::<BlastEvent>()
+            .add_event::<ImpactEvent>()
+            .add_event::<ExplosionEvent>()
+            .add_event::<FireEvent>()
+            .add_event::<StarsEvent>()
+            .add_event::<RestoreEvent>()
+            .add_event::<ContactEvent>()
+            .add_event::<CollisionEvent>()
+            .add_event::<DamageEvent>()
+            .init_resource::<Cache>()
+            .init_resource::<Fragmentation>()
+            .init_resource::<GameRng>()
+            .init_resource::<Intent>()
+            .init_resource::<Mission>()
+            .init_resource::<SharedAssets>()
+            .add_startup_system(camera::spawn)
+            .add_startup_system(keyboard_bindings::spawn)
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                intent::from_keyboard.label(INTENT).after(InputSystem),
+            )
+            .add_system_to_stage(CoreStage::PreUpdate, tick::restore)
+            .add_fixed_timestep_system(
+                FIXED_UPDATE,
+                PRE_UPDATE,
+                collision::mass::update
+                    .run_if(game_state::ingame_or_gameover)
+                    .label(MASS),
+            )
+            .add_fixed_timestep_system(FIXED_UPDATE, PRE_UPDATE, tick::save.after(MASS))
+            .add_enter_system(GameState::InGame, intent::consume)
+            .add_enter_system(GameState::GameSetup, rng::reseed.label(SEED))
+            .add_enter_system(GameState::GameSetup, shared_assets::clear)
+            .add_enter_system_set(
+                GameState::GameSetup,
+                ConditionSet::new()
+                    .after(SEED)
+                    .with_system(spaceship::spawn)
+                    .with_system(boss::spawn)
+                    .with_system(map::spawn)
+                    .with_system(mission::start)
+                    .into(),
+            )
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(GameState::GameSetup)
+                    .with_system(spaceship::flame::front_spawn)
+                    .with_system(spaceship::flame::rear_spawn)
+                    .into(),
+            )
+            .add_system(star::spawn.run_if(game_state::gamesetup_ingame_or_gameover));
+
+        // Systems of a tick run one after the other, always in the same order,
+        // since Bevy would order the systems which do not depend on each other differently
+        // from one app to the other and a replay would not play out like its recording.
+        // MOVEMENT precedes COLLISION, ATTACK, the flames and the camera,
+        // COLLISION precedes IMPACT_EVENT and EXPLOSION, and ATTACK precedes blast and fire spawns.
+        add_fixed_timestep_sequence(
+            app,
+            UPDATE,
+            vec![
+                (Some(FREE), step(map::update)),
+                (Some(FREE), step(game_state::ingame_to_paused)),
+                (Some(FREE), step(asteroid::spawn)),
+                (Some(FREE), step(intercepter::spawn)),
+                (Some(FREE), step(blast::update)),
+                (Some(FREE), step(impact::update)),
+                (Some(FREE), step(wreckage::update)),
+                (Some(FREE), step(wreckage::update_debris)),
+                (Some(MOVEMENT), step(boss::movement)),
+                (Some(MOVEMENT), step(fire::movement)),
+                (Some(MOVEMENT), step(spaceship::movement)),
+                (Some(MOVEMENT), step(asteroid::movement)),
+                (Some(MOVEMENT), step(intercepter::movement)),
+                (Some(MOVEMENT), step(freighter::movement)),
+                (Some(COLLISION), step(collision::generic::with::<Asteroid>)),
+                (
+                    Some(COLLISION),
+                    step(collision::generic::with::<Intercepter>),
+                ),
+                (Some(COLLISION), step(collision::generic::with::<Freighter>)),
+                (
+                    Some(COLLISION),
+                    step(collision::generic::between::<Asteroid, Boss>),
+                ),
+                (
+                    Some(COLLISION),
+                    step(collision::generic::between::<Asteroid, Fire>),
+                ),
+                (
+                    Some(COLLISION),
+                    step(collision::generic::between::<Asteroid, Freighter>),
+                ),
+                (
+                    Some(COLLISION),
+                    step(collision::generic::between::<Asteroid, Intercepter>),
+                ),
+                (
+                    Some(COLLISION),
+                    step(collision::generic::between::<Asteroid, Spaceship>),
+                ),
+                (
+                    Some(COLLISION),
+                    step(collision::generic::between::<Boss, Fire>),
+                ),
+                (
+                    Some(COLLISION),
+                    step(collision::generic::between::<Boss, Freighter>),
+                ),
+                (
+                    Some(COLLISION),
+                    step(collision::generic::between::<Boss, Intercepter>),
+                ),
+                (
+                    Some(COLLISION),
+                    step(collision::generic::between::<Boss, Spaceship>),
+                ),
+                (
+                    Some(COLLISION),
+                    step(collision::generic::between::<Freighter, Fire>),
+                ),
+                (
+                    Some(COLLISION),
+                    step(collision::generic::between::<Freighter, Intercepter>),
+                ),
+                (
+                    Some(COLLISION),
+                    step(collision::generic::between::<Spaceship, Freighter>),
+                ),
+                (
+                    Some(COLLISION),
+                    step(collision::generic::between::<Spaceship, Fire>),
+                ),
+                (
+                    Some(COLLISION),
+                    step(collision::generic::between::<Spaceship, Intercepter>),
+                ),
+                (
+                    Some(COLLISION),
+                    step(collision::generic::between::<Intercepter, Fire>),
+                ),
+                (Some(IMPACT_EVENT), step(fire::impact)),
+                (Some(EXPLOSION), step(explosion::explode)),
+                (None, step(mission::update)),
+                (None, step(game_over::end)),
+                (None, step(asteroid::fragment)),
+                (None, step(impact::spawn)),
+                (Some(ATTACK), step(spaceship::attack)),
+                (Some(ATTACK), step(boss::attack)),
+                (Some(ATTACK), step(intercepter::attack)),
+                (None, step(blast::spawn)),
+                (None, step(fire::spawn)),
+                (None, step(spaceship::flame::front_update)),
+                (None, step(spaceship::flame::rear_update)),
+                (Some(CAMERA), step(camera::update)),
+            ],
+        );
+        add_fixed_timestep_sequence(
+            app,
+            CLEANUP,
+            vec![
+                (None, step(boss::lone_core)),
+                (None, step(collision::cache::update)),
+                (None, step(wreckage::wreck_with::<Spaceship>)),
+                (None, step(wreckage::wreck_with::<Asteroid>)),
+                (None, step(wreckage::wreck_with::<Boss>)),
+                (None, step(wreckage::wreck_with::<Intercepter>)),
+                (None, step(wreckage::wreck_with::<Freighter>)),
+                (None, step(despawn::with::<Blast>)),
+                (None, step(despawn::with::<Impact>)),
+                (None, step(despawn::recursive_with::<Wreckage>)),
+                (None, step(fire::despawn)),
+                (None, step(dormant::store)),
+                (None, step(dormant::restore)),
+            ],
+        );
+
+        app.add_fixed_timestep_system(FIXED_UPDATE, CLEANUP, intent::consume)
+            .add_system_to_stage(CoreStage::PostUpdate, tick::insert_interpolation)
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                tick::interpolate
+                    .label(INTERPOLATION)
+                    .before(TransformSystem::TransformPropagate),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                star::parallax
+                    .after(INTERPOLATION)
+                    .before(TransformSystem::TransformPropagate),
+            );
+    }
+}
+
+// System of a sequence, with the unique label it is ordered by
+fn step<Params>(
+    system: impl IntoConditionalSystem<Params>,
+) -> (&'static str, ConditionalSystemDescriptor) {
+    (type_name_of(&system), system.into_conditional())
+}
+
+fn type_name_of<T>(_: &T) -> &'static str {
+    std::any::type_name::<T>()
+}
+
+// Adds the systems to a substage of FIXED_UPDATE, running in states GameState::InGame
+// and GameState::GameOver, one after the other, each one labelled with the set it belongs to if any
+fn add_fixed_timestep_sequence(
+    app: &mut App,
+    substage: usize,
+    systems: Vec<(
+        Option<&'static str>,
+        (&'static str, ConditionalSystemDescriptor),
+    )>,
+) {
+    let mut previous = None;
+    for (set, (label, system)) in systems {
+        let mut system = system.run_if(game_state::ingame_or_gameover).label(label);
+        if let Some(set) = set {
+            system = system.label(set);
+        }
+        if let Some(previous) = previous {
+            system = system.after(previous);
+        }
+        app.add_fixed_timestep_system(FIXED_UPDATE, substage, system);
+        previous = Some(label);
+    }
+}
+
+/// Menus, lights and HUD. Requires `AsteroidsSimulationPlugin` to be added first.
+pub struct AsteroidsUiPlugin;
+
+impl Plugin for AsteroidsUiPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ActiveWaypoint>()
+            .init_resource::<RadarRange>()
+            .add_enter_system(GameState::MainMenu, ui::main_menu::spawn)
+            .add_enter_system(GameState::Settings, ui::settings_menu::spawn)
+            .add_enter_system(GameState::Paused, ui::pause_menu::spawn)
+            .add_system(ui::main_menu::update.run_in_state(GameState::MainMenu))
+            .add_system(ui::settings_menu::update.run_in_state(GameState::Settings))
+            .add_system(ui::pause_menu::update.run_in_state(GameState::Paused))
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(GameState::GameSetup)
+                    .with_system(compass::spawn)
+                    .with_system(health_bar::spawn)
+                    .with_system(radar::spawn)
+                    .with_system(waypoint::spawn_return)
+                    .with_system(game_state::gamesetup_to_turnuplight)
+                    .into(),
+            )
+            .add_exit_system(GameState::GameSetup, light::kill)
+            .add_enter_system(GameState::TurnUpLight, camera::setup)
+            .add_system(light::turn_up.run_in_state(GameState::TurnUpLight))
+            .add_system(light::turn_down.run_in_state(GameState::TurnDownLight))
+            .add_system_set(
+                ConditionSet::new()
+                    .run_if(game_state::ingame_or_gameover)
+                    .with_system(waypoint::track_nearest_enemy)
+                    .with_system(waypoint::place_marker)
+                    .with_system(compass::cycle)
+                    .into(),
+            )
+            .add_exit_system(GameState::GameSetup, objective::spawn_text)
+            .add_system_set_to_stage(
+                CoreStage::PostUpdate,
+                ConditionSet::new()
+                    .run_if(game_state::ingame_or_gameover)
+                    .after(INTERPOLATION)
+                    .before(TransformSystem::TransformPropagate)
+                    .with_system(compass::update)
+                    .with_system(compass::update_indicators)
+                    .with_system(health_bar::update)
+                    .with_system(radar::update)
+                    .with_system(objective::update_text)
+                    .with_system(game_over::update_text)
+                    .into(),
+            )
+            .add_enter_system(GameState::GameOver, game_over::spawn_text);
+    }
+}
+
+/// Runs the game without any window nor GPU, straight from `GameState::GameSetup`.
+///
+/// The app can be stepped with `App::update`, each update but the first running exactly one tick,
+/// or run in a loop with `App::run`.
+pub struct HeadlessPlugins;
+
+impl PluginGroup for HeadlessPlugins {
+    fn build(self) -> PluginGroupBuilder {
+        PluginGroupBuilder::start::<Self>()
+            .add(bevy::core::CorePlugin::default())
+            .add(bevy::time::TimePlugin)
+            .add(bevy::transform::TransformPlugin)
+            .add(bevy::hierarchy::HierarchyPlugin)
+            .add(bevy::input::InputPlugin)
+            .add(bevy::window::WindowPlugin {
+                add_primary_window: false,
+                exit_on_all_closed: false,
+                ..default()
+            })
+            .add(bevy::asset::AssetPlugin::default())
+            .add(HeadlessPlugin)
+            .add(bevy::render::RenderPlugin)
+            .add(bevy::render::texture::ImagePlugin::default())
+            .add(bevy::core_pipeline::CorePipelinePlugin)
+            .add(bevy::sprite::SpritePlugin)
+            .add(bevy::app::ScheduleRunnerPlugin)
+            .add(AsteroidsSimulationPlugin {
+                initial_state: GameState::GameSetup,
+            })
+    }
+}
+
+/// Keeps the renderer from looking for a GPU, skips the lights between setup and game
+/// and makes each frame last a tick.
+pub struct HeadlessPlugin;
+
+impl Plugin for HeadlessPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(WgpuSettings {
+            backends: None,
+            ..default()
+        })
+        .insert_resource(Lockstep(Duration::from_secs_f32(TIMESTEP)))
+        .add_system(game_state::gamesetup_to_ingame.run_in_state(GameState::GameSetup))
+        .add_system_to_stage(CoreStage::Last, tick::lockstep);
+    }
+}
+
+/// Records the last match played to a file, saved when the app exits.
+/// Requires `AsteroidsSimulationPlugin` to be added first.
+pub struct RecordPlugin {
+    pub path: PathBuf,
+}
+
+impl Plugin for RecordPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Recorder::new(self.path.clone()))
+            .add_enter_system(GameState::GameSetup, replay::restart.after(SEED))
+            .add_fixed_timestep_system(
+                FIXED_UPDATE,
+                PRE_UPDATE,
+                replay::record.run_if(game_state::ingame_or_gameover),
+            )
+            .add_system_to_stage(CoreStage::Last, replay::save);
+    }
+}
+
+/// Plays a recording back in place of the keyboard, with the seed it was recorded with.
+/// Requires `AsteroidsSimulationPlugin` to be added first.
+pub struct ReplayPlugin {
+    pub recording: Recording,
+}
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        if self.recording.timestep != TIMESTEP {
+            panic!(
+                "Recording made with ticks of {} s, the game now runs with ticks of {} s",
+                self.recording.timestep, TIMESTEP
+            );
+        }
+
+        app.insert_resource(GameRng::new(Some(self.recording.seed)))
+            .insert_resource(Replay::new(self.recording.clone()))
+            .add_fixed_timestep_system(
+                FIXED_UPDATE,
+                PRE_UPDATE,
+                replay::replay.run_if(game_state::ingame_or_gameover),
+            );
+    }
+}
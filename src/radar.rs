@@ -0,0 +1,293 @@
+// Radar at the bottom right of the screen, centered on the spaceship.
+//
+// Bodies within range are plotted as blips colored after their faction,
+// over the boundaries of the sectors known so far.
+// Threats out of range are plotted on the rim, in their direction.
+use bevy::{prelude::*, render::mesh::PrimitiveTopology, sprite::Mesh2dHandle};
+
+use crate::{
+    asteroid::Asteroid,
+    boss::Boss,
+    component::Part,
+    constant::{WINDOW_HEIGHT, WINDOW_WIDTH},
+    freighter::Freighter,
+    intercepter::Intercepter,
+    map::Sector,
+    shared_assets::SharedAssets,
+    spaceship::Spaceship,
+    wreckage::Wreckage,
+};
+
+const RADIUS: f32 = 80.0;
+const MARGIN: f32 = 10.0;
+const CENTER: Vec3 = Vec3 {
+    x: WINDOW_WIDTH / 2.0 - RADIUS - MARGIN,
+    y: -WINDOW_HEIGHT / 2.0 + RADIUS + MARGIN,
+    z: 0.0,
+};
+const BACKGROUND_Z: f32 = -3.0;
+const LINES_Z: f32 = -2.0;
+const BLIP_Z: f32 = -1.0;
+const COLOR_BACKGROUND: Color = Color::rgba(0.0, 0.08, 0.0, 0.7);
+const COLOR_RIM: Color = Color::DARK_GREEN;
+const COLOR_SECTORS: Color = Color::rgb(0.1, 0.25, 0.1);
+const RIM_VERTICES: usize = 64;
+const BLIP_RADIUS: f32 = 2.0;
+const BLIP_VERTICES: usize = 8;
+// Scale of the blip of the boss
+const BOSS_SCALE: f32 = 2.0;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Faction {
+    Player,
+    Neutral,
+    Enemy,
+    Wreck,
+}
+
+impl Faction {
+    pub fn color(&self) -> Color {
+        match self {
+            Faction::Player => Color::rgb(0.25, 1.0, 0.25),
+            Faction::Neutral => Color::GRAY,
+            Faction::Enemy => Color::RED,
+            Faction::Wreck => Color::rgb(0.4, 0.3, 0.2),
+        }
+    }
+}
+
+// Distance from the spaceship up to which bodies are plotted
+#[derive(Clone, Copy, Debug, Resource)]
+pub struct RadarRange(pub f32);
+
+impl Default for RadarRange {
+    fn default() -> Self {
+        RadarRange(4000.0)
+    }
+}
+
+#[derive(Component)]
+pub struct Radar;
+
+#[derive(Component)]
+pub struct SectorLines;
+
+#[derive(Component)]
+pub struct Blip;
+
+// Part of line segment [ab] lying in the disk of the given radius centered at the origin
+pub fn clip_to_disk(a: Vec2, b: Vec2, radius: f32) -> Option<(Vec2, Vec2)> {
+    // Points a + t * ab of the line are in the disk if t lies between the roots of
+    // |ab|² t² + 2 a.ab t + |a|² - r² = 0
+    let ab = b - a;
+    let [qa, qb, qc] = [ab.dot(ab), 2.0 * a.dot(ab), a.dot(a) - radius * radius];
+    let discriminant = qb * qb - 4.0 * qa * qc;
+    if qa == 0.0 || discriminant <= 0.0 {
+        return None;
+    }
+
+    let t1 = ((-qb - discriminant.sqrt()) / (2.0 * qa)).max(0.0);
+    let t2 = ((-qb + discriminant.sqrt()) / (2.0 * qa)).min(1.0);
+    if t1 < t2 {
+        Some((a + t1 * ab, a + t2 * ab))
+    } else {
+        None
+    }
+}
+
+pub fn spawn(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    query_camera: Query<Entity, With<Camera>>,
+) {
+    let background = commands
+        .spawn(ColorMesh2dBundle {
+            mesh: meshes
+                .add(Mesh::from(shape::Circle {
+                    radius: RADIUS,
+                    vertices: RIM_VERTICES,
+                }))
+                .into(),
+            transform: Transform::from_xyz(0.0, 0.0, BACKGROUND_Z),
+            material: materials.add(COLOR_BACKGROUND.into()),
+            ..default()
+        })
+        .id();
+
+    let step = std::f32::consts::TAU / RIM_VERTICES as f32;
+    let r_pos: Vec<[f32; 3]> = (0..=RIM_VERTICES)
+        .map(|i| {
+            let (sin, cos) = (i as f32 * step).sin_cos();
+            [RADIUS * cos, RADIUS * sin, 0.0]
+        })
+        .collect();
+    let mut mesh = Mesh::new(PrimitiveTopology::LineStrip);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, r_pos);
+
+    let rim = commands
+        .spawn(ColorMesh2dBundle {
+            mesh: meshes.add(mesh).into(),
+            transform: Transform::from_xyz(0.0, 0.0, LINES_Z),
+            material: materials.add(COLOR_RIM.into()),
+            ..default()
+        })
+        .id();
+
+    let mut mesh = Mesh::new(PrimitiveTopology::LineList);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, vec![[0.0, 0.0, 0.0]; 2]);
+
+    let sector_lines = commands
+        .spawn(SectorLines)
+        .insert(ColorMesh2dBundle {
+            mesh: meshes.add(mesh).into(),
+            transform: Transform::from_xyz(0.0, 0.0, LINES_Z),
+            material: materials.add(COLOR_SECTORS.into()),
+            ..default()
+        })
+        .id();
+
+    let radar = commands
+        .spawn(Radar)
+        .insert(SpatialBundle {
+            transform: Transform::from_translation(CENTER),
+            ..default()
+        })
+        .push_children(&[background, rim, sector_lines])
+        .id();
+
+    commands.entity(query_camera.single()).add_child(radar);
+}
+
+// Blips are recycled from one frame to the next, those left over being hidden.
+// New ones are hidden until the next frame, once their transforms have been propagated.
+pub fn update(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut shared_assets: ResMut<SharedAssets>,
+    mut query_blip: Query<
+        (&mut Handle<ColorMaterial>, &mut Transform, &mut Visibility),
+        With<Blip>,
+    >,
+    query_body: Query<
+        (
+            Option<&Asteroid>,
+            Option<&Boss>,
+            Option<&Freighter>,
+            Option<&Intercepter>,
+            &Transform,
+        ),
+        (
+            Or<(
+                With<Asteroid>,
+                With<Boss>,
+                With<Freighter>,
+                With<Intercepter>,
+                With<Wreckage>,
+            )>,
+            Without<Part>,
+            Without<Blip>,
+        ),
+    >,
+    query_camera: Query<&Transform, (With<Camera>, Without<Blip>)>,
+    query_radar: Query<Entity, With<Radar>>,
+    query_sector: Query<&Transform, (With<Sector>, Without<Blip>)>,
+    query_sector_lines: Query<&Mesh2dHandle, With<SectorLines>>,
+    query_spaceship: Query<&Transform, (With<Spaceship>, Without<Part>, Without<Blip>)>,
+    range: Res<RadarRange>,
+) {
+    let maybe_spaceship = query_spaceship.get_single().ok();
+    let center = maybe_spaceship
+        .unwrap_or_else(|| query_camera.single())
+        .translation
+        .truncate();
+    let scale = RADIUS / range.0;
+
+    let mut plots: Vec<(Vec2, Faction, f32)> = Vec::new();
+    if maybe_spaceship.is_some() {
+        plots.push((Vec2::ZERO, Faction::Player, 1.0));
+    }
+    for (maybe_asteroid, maybe_boss, maybe_freighter, maybe_intercepter, transform) in &query_body {
+        let faction = if maybe_asteroid.is_some() {
+            Faction::Neutral
+        } else if maybe_freighter.is_some() {
+            Faction::Player
+        } else if maybe_boss.is_some() || maybe_intercepter.is_some() {
+            Faction::Enemy
+        } else {
+            Faction::Wreck
+        };
+        let blip_scale = if maybe_boss.is_some() {
+            BOSS_SCALE
+        } else {
+            1.0
+        };
+
+        let offset = (transform.translation.truncate() - center) * scale;
+        if offset.length() <= RADIUS {
+            plots.push((offset, faction, blip_scale));
+        } else if faction == Faction::Enemy {
+            plots.push((offset.clamp_length_max(RADIUS), faction, blip_scale));
+        }
+    }
+
+    let mut blips = query_blip.iter_mut();
+    for (position, faction, blip_scale) in plots {
+        let material = shared_assets.material(&mut materials, faction.color());
+        let transform = Transform::from_translation(position.extend(BLIP_Z))
+            .with_scale(Vec3::splat(blip_scale));
+        match blips.next() {
+            Some((mut blip_material, mut blip_transform, mut visibility)) => {
+                if *blip_material != material {
+                    *blip_material = material;
+                }
+                *blip_transform = transform;
+                visibility.is_visible = true;
+            }
+            None => {
+                let blip = commands
+                    .spawn(Blip)
+                    .insert(ColorMesh2dBundle {
+                        mesh: shared_assets
+                            .circle(&mut meshes, BLIP_RADIUS, BLIP_VERTICES)
+                            .into(),
+                        transform,
+                        material,
+                        visibility: Visibility { is_visible: false },
+                        ..default()
+                    })
+                    .id();
+                commands.entity(query_radar.single()).add_child(blip);
+            }
+        }
+    }
+    for (_, _, mut visibility) in blips {
+        visibility.is_visible = false;
+    }
+
+    let [hw, hh] = [WINDOW_WIDTH / 2.0 * scale, WINDOW_HEIGHT / 2.0 * scale];
+    let mut l_pos: Vec<[f32; 3]> = Vec::new();
+    for transform in &query_sector {
+        let c = (transform.translation.truncate() - center) * scale;
+        let corners = [
+            c + Vec2::new(-hw, -hh),
+            c + Vec2::new(hw, -hh),
+            c + Vec2::new(hw, hh),
+            c + Vec2::new(-hw, hh),
+        ];
+        for (a, b) in corners.iter().zip(corners.iter().cycle().skip(1)) {
+            if let Some((a, b)) = clip_to_disk(*a, *b, RADIUS) {
+                l_pos.push(a.extend(0.0).to_array());
+                l_pos.push(b.extend(0.0).to_array());
+            }
+        }
+    }
+    if l_pos.is_empty() {
+        l_pos = vec![[0.0, 0.0, 0.0]; 2];
+    }
+    meshes
+        .get_mut(&query_sector_lines.single().0)
+        .unwrap()
+        .insert_attribute(Mesh::ATTRIBUTE_POSITION, l_pos);
+}
@@ -0,0 +1,168 @@
+use bevy::{app::AppExit, prelude::*};
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::{constant::TIMESTEP, intent::Intent, rng::GameRng};
+
+const MAGIC: &[u8; 4] = b"ASTR";
+const VERSION: u8 = 1;
+
+// A match as played: its seed, the duration of its ticks and the intent of the player
+// at each tick spent in GameState::InGame or GameState::GameOver.
+//
+// File layout, little endian: magic, version, seed (u64), timestep (f32),
+// then runs of identical intents as (length: u8, intent bits: u8) pairs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Recording {
+    pub seed: u64,
+    pub timestep: f32,
+    pub intents: Vec<Intent>,
+}
+
+impl Recording {
+    pub fn write(&self, mut writer: impl Write) -> io::Result<()> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[VERSION])?;
+        writer.write_all(&self.seed.to_le_bytes())?;
+        writer.write_all(&self.timestep.to_le_bytes())?;
+
+        let mut intents = self.intents.iter().peekable();
+        while let Some(intent) = intents.next() {
+            let mut length = 1;
+            while length < u8::MAX && intents.next_if_eq(&intent).is_some() {
+                length += 1;
+            }
+            writer.write_all(&[length, intent.to_bits()])?;
+        }
+
+        Ok(())
+    }
+
+    pub fn read(mut reader: impl Read) -> io::Result<Recording> {
+        let mut header = [0; 17];
+        reader.read_exact(&mut header)?;
+        if &header[0..4] != MAGIC || header[4] != VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "not a recording of this version of the game",
+            ));
+        }
+        let seed = u64::from_le_bytes(header[5..13].try_into().unwrap());
+        let timestep = f32::from_le_bytes(header[13..17].try_into().unwrap());
+
+        let mut runs = Vec::new();
+        reader.read_to_end(&mut runs)?;
+        if runs.len() % 2 != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "truncated recording",
+            ));
+        }
+        let intents = runs
+            .chunks_exact(2)
+            .flat_map(|run| std::iter::repeat_n(Intent::from_bits(run[1]), run[0] as usize))
+            .collect();
+
+        Ok(Recording {
+            seed,
+            timestep,
+            intents,
+        })
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write(&mut writer)?;
+        writer.flush()
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Recording> {
+        Recording::read(BufReader::new(File::open(path)?))
+    }
+}
+
+// Records the current match, saved to `path` when the app exits
+#[derive(Debug, Resource)]
+pub struct Recorder {
+    pub path: PathBuf,
+    recording: Recording,
+}
+
+impl Recorder {
+    pub fn new(path: PathBuf) -> Recorder {
+        Recorder {
+            path,
+            recording: Recording {
+                seed: 0,
+                timestep: TIMESTEP,
+                intents: Vec::new(),
+            },
+        }
+    }
+
+    pub fn recording(&self) -> &Recording {
+        &self.recording
+    }
+}
+
+// Plays a recording back in place of the keyboard
+#[derive(Debug, Resource)]
+pub struct Replay {
+    recording: Recording,
+    tick: usize,
+}
+
+impl Replay {
+    pub fn new(recording: Recording) -> Replay {
+        Replay { recording, tick: 0 }
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.tick >= self.recording.intents.len()
+    }
+}
+
+// Starts recording the match about to be set up, dropping any previous one
+pub fn restart(mut recorder: ResMut<Recorder>, game_rng: Res<GameRng>) {
+    recorder.recording.seed = game_rng.seed();
+    recorder.recording.intents.clear();
+}
+
+pub fn record(mut recorder: ResMut<Recorder>, intent: Res<Intent>) {
+    recorder.recording.intents.push(*intent);
+}
+
+pub fn save(mut exit: EventReader<AppExit>, recorder: Res<Recorder>) {
+    if exit.iter().next().is_some() {
+        match recorder.recording.save(&recorder.path) {
+            Ok(()) => info!(
+                "Recorded {} ticks to {}",
+                recorder.recording.intents.len(),
+                recorder.path.display()
+            ),
+            Err(error) => error!(
+                "Cannot save recording to {}: {}",
+                recorder.path.display(),
+                error
+            ),
+        }
+    }
+}
+
+// Pausing is left to the keyboard since it has no bearing on the match
+pub fn replay(mut intent: ResMut<Intent>, mut replay: ResMut<Replay>) {
+    let pause = intent.pause;
+    if let Some(recorded) = replay.recording.intents.get(replay.tick) {
+        *intent = *recorded;
+        replay.tick += 1;
+        if replay.is_over() {
+            info!("End of replay");
+        }
+    } else {
+        *intent = Intent::default();
+    }
+    intent.pause = pause;
+}
@@ -0,0 +1,89 @@
+use bevy::prelude::*;
+use rand::Rng;
+use rand_pcg::Pcg32;
+
+/// Source of every random number of a match.
+///
+/// A match is entirely determined by its seed and the inputs of the player.
+/// Each system draws from its own stream, forked from the seed under a name,
+/// so the order in which systems run does not matter.
+#[derive(Debug, Resource)]
+pub struct GameRng {
+    seed: u64,
+    fixed: bool,
+    generation: u32,
+}
+
+impl GameRng {
+    /// Every match will be played with the given seed or with a fresh random seed if none.
+    pub fn new(seed: Option<u64>) -> GameRng {
+        GameRng {
+            seed: seed.unwrap_or_else(|| rand::thread_rng().gen()),
+            fixed: seed.is_some(),
+            generation: 0,
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn fork(&self, stream: &str) -> Pcg32 {
+        Pcg32::new(self.seed, hash(stream.as_bytes()))
+    }
+
+    /// Starts the streams over, with a new seed unless it was fixed.
+    pub fn reseed(&mut self) {
+        if !self.fixed {
+            self.seed = rand::thread_rng().gen();
+        }
+        self.generation += 1;
+    }
+}
+
+impl Default for GameRng {
+    fn default() -> GameRng {
+        GameRng::new(None)
+    }
+}
+
+/// Stream of a system, forked from `GameRng` on first use and again after each reseed.
+///
+/// Meant to be held as a `Local`.
+#[derive(Default)]
+pub struct Stream(Option<(u32, Pcg32)>);
+
+impl Stream {
+    pub fn get(&mut self, game_rng: &GameRng, name: &str) -> &mut Pcg32 {
+        self.get_with(game_rng, || name)
+    }
+
+    /// Same as `get`, with a name only built when the stream is forked.
+    pub fn get_with<S: AsRef<str>>(
+        &mut self,
+        game_rng: &GameRng,
+        name: impl FnOnce() -> S,
+    ) -> &mut Pcg32 {
+        if self
+            .0
+            .as_ref()
+            .is_none_or(|(generation, _)| *generation != game_rng.generation)
+        {
+            self.0 = Some((game_rng.generation, game_rng.fork(name().as_ref())));
+        }
+
+        &mut self.0.as_mut().unwrap().1
+    }
+}
+
+pub fn reseed(mut game_rng: ResMut<GameRng>) {
+    game_rng.reseed();
+    info!("Seed: {}", game_rng.seed());
+}
+
+// FNV-1a, which unlike the std hasher is stable across builds and platforms
+pub(crate) fn hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
@@ -0,0 +1,61 @@
+// Meshes and materials shared by the many entities drawn alike:
+// stars, fires, blasts, impacts and debris.
+//
+// Entities of the same color share a material: tinting one of them means giving it
+// the material of its new color, never editing the material it holds,
+// which would tint all the others along with it.
+// Circles are rounded to the nearest eighth of a pixel for their meshes to be shared as well.
+use bevy::{prelude::*, utils::HashMap};
+
+const RADIUS_STEP: f32 = 0.125;
+
+#[derive(Default, Resource)]
+pub struct SharedAssets {
+    circles: HashMap<(u32, usize), Handle<Mesh>>,
+    materials: HashMap<[u32; 4], Handle<ColorMaterial>>,
+}
+
+impl SharedAssets {
+    pub fn circle(
+        &mut self,
+        meshes: &mut Assets<Mesh>,
+        radius: f32,
+        vertices: usize,
+    ) -> Handle<Mesh> {
+        let steps = (radius / RADIUS_STEP).round().max(1.0) as u32;
+        self.circles
+            .entry((steps, vertices))
+            .or_insert_with(|| {
+                meshes.add(Mesh::from(shape::Circle {
+                    radius: steps as f32 * RADIUS_STEP,
+                    vertices,
+                }))
+            })
+            .clone()
+    }
+
+    pub fn material(
+        &mut self,
+        materials: &mut Assets<ColorMaterial>,
+        color: Color,
+    ) -> Handle<ColorMaterial> {
+        self.materials
+            .entry(color.as_rgba_f32().map(f32::to_bits))
+            .or_insert_with(|| materials.add(color.into()))
+            .clone()
+    }
+
+    pub fn circles(&self) -> usize {
+        self.circles.len()
+    }
+
+    pub fn materials(&self) -> usize {
+        self.materials.len()
+    }
+}
+
+// Every match starts with fresh assets,
+// those of the previous one having been faded out by the lights.
+pub fn clear(mut shared_assets: ResMut<SharedAssets>) {
+    *shared_assets = SharedAssets::default();
+}
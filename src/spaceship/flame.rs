@@ -1,7 +1,7 @@
 use bevy::{prelude::*, render::mesh::PrimitiveTopology, sprite::Mesh2dHandle};
 
 use super::{Spaceship, S10, S13, S14, S7, S9};
-use crate::{component::Part, keyboard_bindings::KeyboardBindings};
+use crate::{component::Part, intent::Intent};
 
 const COLOR: Color = Color::YELLOW;
 
@@ -76,9 +76,8 @@ pub fn front_spawn(
 
 pub fn rear_update(
     mut meshes: ResMut<Assets<Mesh>>,
-    keys: Res<Input<KeyCode>>,
+    intent: Res<Intent>,
     query: Query<&Mesh2dHandle, With<FlameRear>>,
-    query_bindings: Query<&KeyboardBindings>,
 ) {
     if let Ok(mesh) = query.get_single() {
         if let Some(bevy::render::mesh::VertexAttributeValues::Float32x3(vertices)) = meshes
@@ -86,7 +85,7 @@ pub fn rear_update(
             .unwrap()
             .attribute_mut(Mesh::ATTRIBUTE_POSITION)
         {
-            if keys.any_pressed([query_bindings.single().accelerate(), KeyCode::Up]) {
+            if intent.accelerate {
                 if vertices[0][0] > -20.0 {
                     vertices[0][0] -= 4.0;
                 } else {
@@ -99,13 +98,9 @@ pub fn rear_update(
     }
 }
 
-pub fn front_update(
-    mut query: Query<&mut Transform, With<FlameFront>>,
-    keys: Res<Input<KeyCode>>,
-    query_bindings: Query<&KeyboardBindings>,
-) {
+pub fn front_update(mut query: Query<&mut Transform, With<FlameFront>>, intent: Res<Intent>) {
     for mut transform in query.iter_mut() {
-        if keys.any_pressed([query_bindings.single().decelerate(), KeyCode::Down]) {
+        if intent.decelerate {
             if transform.scale.x < 10.0 {
                 transform.scale.x += 4.0;
                 transform.scale.y += 4.0;
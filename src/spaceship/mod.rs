@@ -1,13 +1,14 @@
 use bevy::{prelude::*, render::mesh::PrimitiveTopology, sprite::Mesh2dHandle};
-use std::f32::consts::PI;
+use iyes_loopless::prelude::*;
 
 use crate::{
     blast::BlastEvent,
     collision::detection::{triangle::Triangle, Aabb, Collider, Topology},
-    component::{AngularVelocity, Health, Mass, MomentOfInertia, Part, Velocity},
+    component::{AngularVelocity, Health, Part, PhysicsMaterial, Velocity},
     constant::{WINDOW_HEIGHT, WINDOW_WIDTH, WINDOW_Z},
     fire::{Fire, FireEvent},
-    keyboard_bindings::KeyboardBindings,
+    intent::Intent,
+    tick,
 };
 
 pub mod flame;
@@ -116,16 +117,8 @@ pub fn spawn(
     mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, v_pos);
     let mesh_handle = meshes.add(mesh);
 
-    const AREA: f32 =
-        (S2.x - S4.x) * S4.y + (S6.x - S8.x) * S8.y + (S10.x - S11.x) * (S10.y - S9.y)
-            - (S6.x + 13.0) * 12.0;
-    const MASS: f32 = AREA;
-    const MOMENT_OF_INERTIA: f32 = 0.5 * MASS * AREA / PI;
-
     let spaceship = commands
         .spawn(Spaceship)
-        .insert(Mass(MASS))
-        .insert(MomentOfInertia(MOMENT_OF_INERTIA))
         .insert(Velocity(Vec3::ZERO))
         .insert(AngularVelocity(0.0))
         .insert(SpatialBundle {
@@ -139,6 +132,7 @@ pub fn spawn(
     let spaceship_part = commands
         .spawn((Spaceship, Part))
         .insert(Health(HEALTH))
+        .insert(PhysicsMaterial::HULL)
         .insert(Collider {
             aabb: AABB,
             topology: Topology::Triangles {
@@ -158,25 +152,30 @@ pub fn spawn(
 pub fn attack(
     mut blast_event: EventWriter<BlastEvent>,
     mut fire_event: EventWriter<FireEvent>,
-    keys: Res<Input<KeyCode>>,
+    intent: Res<Intent>,
     query_spaceship: Query<&Transform, (With<Spaceship>, Without<Part>)>,
-    query_bindings: Query<&KeyboardBindings>,
+    query_spaceship_part: Query<&Transform, (With<Spaceship>, With<Part>)>,
 ) {
-    if !keys.just_pressed(query_bindings.single().fire()) {
+    if !intent.fire {
         return;
     }
 
-    if let Ok(transform) = query_spaceship.get_single() {
+    if let (Ok(s_transform), Ok(sp_transform)) = (
+        query_spaceship.get_single(),
+        query_spaceship_part.get_single(),
+    ) {
         const BLAST_RADIUS: f32 = 8.0;
         const BLAST_VERTICES: usize = 8;
         const ATTACK_COLOR: Color = Color::YELLOW;
         const ATTACK_SOURCE: Vec3 = S2;
+        let attack_absolute_translation =
+            s_transform.transform_point(sp_transform.transform_point(ATTACK_SOURCE));
 
         blast_event.send(BlastEvent {
             radius: BLAST_RADIUS,
             vertices: BLAST_VERTICES,
             color: ATTACK_COLOR,
-            translation: transform.translation + transform.rotation * ATTACK_SOURCE,
+            translation: attack_absolute_translation,
         });
 
         const FIRE_IMPACT_RADIUS: f32 = 12.0;
@@ -202,8 +201,8 @@ pub fn attack(
             vertices: FIRE_VERTICES,
             color: ATTACK_COLOR,
             range: FIRE_RANGE as f32,
-            translation: transform.translation + transform.rotation * ATTACK_SOURCE,
-            velocity: Velocity(transform.rotation * FIRE_VELOCITY),
+            translation: attack_absolute_translation,
+            velocity: Velocity(s_transform.rotation * FIRE_VELOCITY),
         });
     }
 }
@@ -213,37 +212,34 @@ pub fn movement(
         (&mut AngularVelocity, &mut Transform, &mut Velocity),
         (With<Spaceship>, Without<Part>),
     >,
-    keys: Res<Input<KeyCode>>,
-    query_bindings: Query<&KeyboardBindings>,
-    time: Res<Time>,
+    intent: Res<Intent>,
+    fixed_timesteps: Res<FixedTimesteps>,
 ) {
-    let bindings = query_bindings.single();
-
+    let delta = tick::delta(&fixed_timesteps);
     if let Ok((mut s_angular_velocity, mut s_transform, mut s_velocity)) =
         query_spaceship.get_single_mut()
     {
         const ROTATION_SPEED: f32 = 20.0;
-        if keys.any_pressed([bindings.rotate_left(), KeyCode::Left]) {
-            s_angular_velocity.0 += ROTATION_SPEED * time.delta_seconds();
-        } else if keys.any_pressed([bindings.rotate_right(), KeyCode::Right]) {
-            s_angular_velocity.0 -= ROTATION_SPEED * time.delta_seconds();
+        if intent.rotate_left {
+            s_angular_velocity.0 += ROTATION_SPEED * delta;
+        } else if intent.rotate_right {
+            s_angular_velocity.0 -= ROTATION_SPEED * delta;
         }
 
         const ACCELERATION: f32 = 500.0;
-        if keys.any_pressed([bindings.accelerate(), KeyCode::Up]) {
-            s_velocity.0 += ACCELERATION * time.delta_seconds() * (s_transform.rotation * Vec3::X);
-        } else if keys.any_pressed([bindings.decelerate(), KeyCode::Down]) {
-            s_velocity.0 +=
-                0.5 * ACCELERATION * time.delta_seconds() * (s_transform.rotation * Vec3::NEG_X);
+        if intent.accelerate {
+            s_velocity.0 += ACCELERATION * delta * (s_transform.rotation * Vec3::X);
+        } else if intent.decelerate {
+            s_velocity.0 += 0.5 * ACCELERATION * delta * (s_transform.rotation * Vec3::NEG_X);
         }
 
-        const DRAG: f32 = 0.01;
-        s_velocity.0 *= 1.0 - DRAG;
-        const ANGULAR_DRAG: f32 = 0.1;
-        s_angular_velocity.0 *= 1.0 - ANGULAR_DRAG;
+        // Velocities decay exponentially at these rates, in s⁻¹
+        const DRAG: f32 = 0.6;
+        s_velocity.0 *= (-DRAG * delta).exp();
+        const ANGULAR_DRAG: f32 = 6.3;
+        s_angular_velocity.0 *= (-ANGULAR_DRAG * delta).exp();
 
-        s_transform.translation += s_velocity.0 * time.delta_seconds();
-        s_transform.rotation *=
-            Quat::from_axis_angle(Vec3::Z, s_angular_velocity.0 * time.delta_seconds());
+        s_transform.translation += s_velocity.0 * delta;
+        s_transform.rotation *= Quat::from_axis_angle(Vec3::Z, s_angular_velocity.0 * delta);
     }
 }
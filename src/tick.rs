@@ -0,0 +1,92 @@
+use bevy::{prelude::*, time::TimeUpdateStrategy, utils::Duration};
+use iyes_loopless::prelude::*;
+
+use crate::{component::Velocity, plugin::FIXED_UPDATE};
+
+// Transforms of a moving entity at the end of the last two ticks.
+// The rendered transform is interpolated between them and the simulated one restored each frame.
+#[derive(Clone, Copy, Component, Debug)]
+pub struct Interpolation {
+    previous: Transform,
+    current: Transform,
+}
+
+// Forces every frame to last exactly the given duration, hence to run exactly one tick
+// if it matches the fixed timestep
+#[derive(Clone, Copy, Debug, Resource)]
+pub struct Lockstep(pub Duration);
+
+pub fn lockstep(
+    mut update_strategy: ResMut<TimeUpdateStrategy>,
+    lockstep: Res<Lockstep>,
+    time: Res<Time>,
+) {
+    *update_strategy = TimeUpdateStrategy::ManualInstant(
+        time.last_update().unwrap_or_else(|| time.startup()) + lockstep.0,
+    );
+}
+
+// Length in seconds of the tick being run, for the systems of FIXED_UPDATE
+pub fn delta(fixed_timesteps: &FixedTimesteps) -> f32 {
+    fixed_timesteps
+        .get_current()
+        .unwrap()
+        .timestep()
+        .as_secs_f32()
+}
+
+pub fn insert_interpolation(
+    mut commands: Commands,
+    query: Query<
+        (Entity, &Transform),
+        (Or<(With<Velocity>, With<Camera>)>, Without<Interpolation>),
+    >,
+) {
+    for (entity, transform) in &query {
+        commands.entity(entity).insert(Interpolation {
+            previous: *transform,
+            current: *transform,
+        });
+    }
+}
+
+// Puts back the simulated transforms before any tick of the frame
+pub fn restore(mut query: Query<(&Interpolation, &mut Transform)>) {
+    for (interpolation, mut transform) in &mut query {
+        *transform = interpolation.current;
+    }
+}
+
+// Runs first in each tick
+pub fn save(mut query: Query<(&mut Interpolation, &Transform)>) {
+    for (mut interpolation, transform) in &mut query {
+        interpolation.previous = *transform;
+    }
+}
+
+pub fn interpolate(
+    mut query: Query<(&mut Interpolation, &mut Transform)>,
+    fixed_timesteps: Res<FixedTimesteps>,
+) {
+    let info = fixed_timesteps.get(FIXED_UPDATE).unwrap();
+    // Fraction of a tick elapsed since the last one
+    let s = (info.overstep() as f32).min(1.0);
+
+    for (mut interpolation, mut transform) in &mut query {
+        interpolation.current = *transform;
+        *transform = Transform {
+            translation: interpolation
+                .previous
+                .translation
+                .lerp(interpolation.current.translation, s),
+            rotation: interpolation
+                .previous
+                .rotation
+                .slerp(interpolation.current.rotation, s),
+            scale: interpolation
+                .previous
+                .scale
+                .lerp(interpolation.current.scale, s),
+        };
+    }
+}
@@ -90,7 +90,7 @@ pub fn spawn(
         })
         .id();
 
-    const LEFT_SECTIONS: [&str; 7] = [
+    const LEFT_SECTIONS: [&str; BINDINGS] = [
         "Accelerate",
         "Decelerate",
         "Rotate left",
@@ -98,6 +98,8 @@ pub fn spawn(
         "Fire",
         "Switch camera position",
         "Pause",
+        "Next compass target",
+        "Place marker",
     ];
 
     for left_section in LEFT_SECTIONS {
@@ -140,7 +142,7 @@ pub fn spawn(
         })
         .id();
 
-    const RIGHT_SECTIONS: [&str; 7] = [
+    const RIGHT_SECTIONS: [&str; BINDINGS] = [
         "Up or ",
         "Down or ",
         "Left or ",
@@ -148,6 +150,8 @@ pub fn spawn(
         "",
         "",
         "Esc or ",
+        "",
+        "",
     ];
     let bindings = query_bindings.single().0;
 
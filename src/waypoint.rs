@@ -0,0 +1,131 @@
+// Places tracked by the compass: the objectives, the nearest enemy,
+// the point the spaceship started from and the markers placed by the player.
+use bevy::prelude::*;
+
+use crate::{
+    component::Part,
+    intercepter::Intercepter,
+    keyboard_bindings::KeyboardBindings,
+    spaceship::{self, Spaceship},
+};
+
+// Markers placed beyond that number replace the oldest ones
+const MARKERS: usize = 3;
+
+// Kinds are ordered as the compass cycles through them
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum WaypointKind {
+    Objective,
+    NearestEnemy,
+    Return,
+    Marker,
+}
+
+impl WaypointKind {
+    pub fn color(&self) -> Color {
+        match self {
+            WaypointKind::Objective => Color::ORANGE_RED,
+            WaypointKind::NearestEnemy => Color::RED,
+            WaypointKind::Return => Color::rgb(0.25, 1.0, 0.25),
+            WaypointKind::Marker => Color::YELLOW,
+        }
+    }
+}
+
+#[derive(Clone, Component, Debug)]
+pub struct Waypoint {
+    pub kind: WaypointKind,
+    pub label: String,
+}
+
+impl Waypoint {
+    pub fn new(kind: WaypointKind, label: &str) -> Waypoint {
+        Waypoint {
+            kind,
+            label: label.to_string(),
+        }
+    }
+}
+
+// Markers are numbered in the order they are placed
+#[derive(Component)]
+pub struct Marker(u32);
+
+pub fn spawn_return(mut commands: Commands) {
+    commands
+        .spawn(Waypoint::new(WaypointKind::Return, "Return"))
+        .insert(TransformBundle::from_transform(
+            Transform::from_translation(spaceship::TRANSLATION),
+        ));
+}
+
+// Moves the waypoint of the nearest enemy from one intercepter to the other
+pub fn track_nearest_enemy(
+    mut commands: Commands,
+    query_intercepter: Query<
+        (Entity, &Transform, Option<&Waypoint>),
+        (With<Intercepter>, Without<Part>),
+    >,
+    query_spaceship: Query<&Transform, (With<Spaceship>, Without<Part>)>,
+) {
+    let s_translation = match query_spaceship.get_single() {
+        Ok(transform) => transform.translation,
+        Err(_) => return,
+    };
+    let nearest = query_intercepter
+        .iter()
+        .min_by(|(_, t1, _), (_, t2, _)| {
+            t1.translation
+                .distance_squared(s_translation)
+                .total_cmp(&t2.translation.distance_squared(s_translation))
+        })
+        .map(|(intercepter, ..)| intercepter);
+
+    for (intercepter, _, maybe_waypoint) in &query_intercepter {
+        match (Some(intercepter) == nearest, maybe_waypoint.is_some()) {
+            (true, false) => {
+                commands
+                    .entity(intercepter)
+                    .insert(Waypoint::new(WaypointKind::NearestEnemy, "Nearest enemy"));
+            }
+            (false, true) => {
+                commands.entity(intercepter).remove::<Waypoint>();
+            }
+            _ => {}
+        }
+    }
+}
+
+pub fn place_marker(
+    mut commands: Commands,
+    mut placed: Local<u32>,
+    input: Res<Input<KeyCode>>,
+    query_bindings: Query<&KeyboardBindings>,
+    query_marker: Query<(Entity, &Marker)>,
+    query_spaceship: Query<&Transform, (With<Spaceship>, Without<Part>)>,
+) {
+    if !input.just_pressed(query_bindings.single().place_marker()) {
+        return;
+    }
+    let s_transform = match query_spaceship.get_single() {
+        Ok(transform) => transform,
+        Err(_) => return,
+    };
+
+    if query_marker.iter().count() >= MARKERS {
+        if let Some((oldest, _)) = query_marker.iter().min_by_key(|(_, marker)| marker.0) {
+            commands.entity(oldest).despawn();
+        }
+    }
+
+    *placed += 1;
+    commands
+        .spawn(Waypoint::new(
+            WaypointKind::Marker,
+            &format!("Marker {}", *placed),
+        ))
+        .insert(Marker(*placed))
+        .insert(TransformBundle::from_transform(
+            Transform::from_translation(s_transform.translation),
+        ));
+}
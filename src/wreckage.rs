@@ -1,11 +1,15 @@
-use bevy::{prelude::*, render::mesh::VertexAttributeValues};
+use bevy::prelude::*;
+use iyes_loopless::prelude::*;
 use rand::Rng;
 use std::f32::consts::PI;
 
 use crate::{
-    collision::detection::{triangle::TriangleXY, Collider, Topology},
+    collision::detection::{Collider, Topology},
     component::{AngularVelocity, Health, Part, Velocity},
-    transform,
+    explosion::{ExplosionEvent, Explosive},
+    rng::{GameRng, Stream},
+    shared_assets::SharedAssets,
+    tick, transform,
 };
 
 const HEALTH: u32 = 100;
@@ -19,26 +23,29 @@ pub struct WreckageDebris;
 
 pub fn update_debris(
     mut query: Query<(&mut Transform, &Velocity), With<WreckageDebris>>,
-    time: Res<Time>,
+    fixed_timesteps: Res<FixedTimesteps>,
 ) {
+    let delta = tick::delta(&fixed_timesteps);
     for (mut transform, velocity) in &mut query {
         transform.scale -= 1.0 / HEALTH as f32;
-        transform.translation += velocity.0 * time.delta_seconds();
+        transform.translation += velocity.0 * delta;
     }
 }
 
 pub fn update(
     mut query: Query<(&mut Health, &mut Transform, &Velocity), With<Wreckage>>,
-    time: Res<Time>,
+    fixed_timesteps: Res<FixedTimesteps>,
 ) {
+    let delta = tick::delta(&fixed_timesteps);
     for (mut health, mut transform, velocity) in &mut query {
         health.0 -= 1;
-        transform.translation += velocity.0 * time.delta_seconds();
+        transform.translation += velocity.0 * delta;
     }
 }
 
 pub fn wreck_with<C: Component>(
     mut commands: Commands,
+    mut explosion_event: EventWriter<ExplosionEvent>,
     mut meshes: ResMut<Assets<Mesh>>,
     query: Query<
         (&AngularVelocity, &Children, Entity, &Transform, &Velocity),
@@ -49,19 +56,25 @@ pub fn wreck_with<C: Component>(
             &Handle<ColorMaterial>,
             &Collider,
             Entity,
+            Option<&Explosive>,
             &Health,
             &Parent,
             &Transform,
         ),
         (With<C>, With<Part>),
     >,
+    mut shared_assets: ResMut<SharedAssets>,
+    mut stream: Local<Stream>,
+    game_rng: Res<GameRng>,
 ) {
-    for (color, collider, part, health, parent, transform) in &query_part {
+    let rng = stream.get_with(&game_rng, || {
+        format!("wreckage::wreck_with::<{}>", std::any::type_name::<C>())
+    });
+    for (color, collider, part, maybe_explosive, health, parent, transform) in &query_part {
         if health.0 > 0 {
             continue;
         }
 
-        let mut rng = rand::thread_rng();
         let (p_angular_velocity, children, parent, p_transform, p_velocity) =
             query.get(**parent).unwrap();
 
@@ -77,6 +90,13 @@ pub fn wreck_with<C: Component>(
             commands.entity(parent).despawn_recursive();
         }
 
+        if let Some(explosive) = maybe_explosive {
+            explosion_event.send(ExplosionEvent {
+                explosive: *explosive,
+                translation: transform::global_of(*transform, *p_transform).translation,
+            });
+        }
+
         let wreckage = commands
             .spawn(Wreckage)
             .insert(Health(HEALTH))
@@ -92,41 +112,32 @@ pub fn wreck_with<C: Component>(
             .id();
 
         match &collider.topology {
-            Topology::Triangles { mesh_handle } => {
-                if let Some(VertexAttributeValues::Float32x3(vertices)) = meshes
-                    .get(&mesh_handle.0)
-                    .unwrap()
-                    .attribute(Mesh::ATTRIBUTE_POSITION)
-                {
-                    for triplet in vertices.clone().chunks_exact(3) {
-                        let triangle: TriangleXY =
-                            <[_; 3]>::try_from(triplet).expect("3 items").into();
-
-                        for _ in 0..(triangle.area() * DEBRIS_PER_SQUARE_UNIT).round() as usize {
-                            let p = triangle.random_point();
-                            let debris =
-                                Vec3::new(p.x, p.y, if rng.gen_bool(0.5) { 1.0 } else { -1.0 });
-                            let dv =
-                                Vec3::new(rng.gen_range(-0.5..0.5), rng.gen_range(-0.5..0.5), 0.0);
-
-                            let debris = commands
-                                .spawn(WreckageDebris)
-                                .insert(Velocity(dv))
-                                .insert(ColorMesh2dBundle {
-                                    mesh: meshes
-                                        .add(Mesh::from(shape::Circle {
-                                            radius: rng.gen_range(1.0..10.0),
-                                            vertices: 4 * rng.gen_range(1..5),
-                                        }))
-                                        .into(),
-                                    transform: Transform::from_translation(debris),
-                                    material: color.clone(),
-                                    ..default()
-                                })
-                                .id();
-
-                            commands.entity(wreckage).add_child(debris);
-                        }
+            Topology::Triangles { .. } | Topology::ConvexPolygon { .. } => {
+                for triangle in collider.topology.triangles(&meshes) {
+                    for _ in 0..(triangle.area() * DEBRIS_PER_SQUARE_UNIT).round() as usize {
+                        let p = triangle.random_point(rng);
+                        let debris =
+                            Vec3::new(p.x, p.y, if rng.gen_bool(0.5) { 1.0 } else { -1.0 });
+                        let dv = Vec3::new(rng.gen_range(-0.5..0.5), rng.gen_range(-0.5..0.5), 0.0);
+
+                        let debris = commands
+                            .spawn(WreckageDebris)
+                            .insert(Velocity(dv))
+                            .insert(ColorMesh2dBundle {
+                                mesh: shared_assets
+                                    .circle(
+                                        &mut meshes,
+                                        rng.gen_range(1.0..10.0),
+                                        4 * rng.gen_range(1..5),
+                                    )
+                                    .into(),
+                                transform: Transform::from_translation(debris),
+                                material: color.clone(),
+                                ..default()
+                            })
+                            .id();
+
+                        commands.entity(wreckage).add_child(debris);
                     }
                 }
             }
@@ -146,11 +157,12 @@ pub fn wreck_with<C: Component>(
                         .spawn(WreckageDebris)
                         .insert(Velocity(dv))
                         .insert(ColorMesh2dBundle {
-                            mesh: meshes
-                                .add(Mesh::from(shape::Circle {
-                                    radius: rng.gen_range(1.0..radius / 10.0),
-                                    vertices: 4 * rng.gen_range(1..5),
-                                }))
+                            mesh: shared_assets
+                                .circle(
+                                    &mut meshes,
+                                    rng.gen_range(1.0..radius / 10.0),
+                                    4 * rng.gen_range(1..5),
+                                )
                                 .into(),
                             transform: Transform::from_translation(debris),
                             material: color.clone(),
@@ -0,0 +1,124 @@
+use asteroids::{
+    asteroid::{self, Fragmentation},
+    collision, wreckage, *,
+};
+use bevy::prelude::*;
+
+#[test]
+fn outline() {
+    let game_rng = GameRng::new(Some(1234));
+    let outline = asteroid::outline(&mut game_rng.fork("outline"), 100.0);
+    assert_eq!(
+        outline,
+        asteroid::outline(&mut game_rng.fork("outline"), 100.0)
+    );
+
+    // Lumpy but star-shaped around the center, in CCW order
+    let distances: Vec<f32> = outline.iter().map(|vertex| vertex.length()).collect();
+    assert!(distances.iter().all(|d| *d > 60.0 && *d < 140.0));
+    assert!(distances.iter().any(|d| (*d - 100.0).abs() > 1.0));
+    // Turning once around the center
+    let winding: f32 = outline
+        .iter()
+        .zip(outline.iter().cycle().skip(1))
+        .map(|(a, b)| {
+            assert!(a.perp_dot(*b) > 0.0);
+            a.angle_between(*b)
+        })
+        .sum();
+    assert!((winding - std::f32::consts::TAU).abs() < 1e-3);
+}
+
+// Asteroid of the given radius at the origin, destroyed once spawned,
+// with the systems computing masses, fragmenting asteroids and wrecking them
+fn destroyed(radius: f32) -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(bevy::asset::AssetPlugin::default())
+        .add_plugin(bevy::transform::TransformPlugin)
+        .add_plugin(bevy::hierarchy::HierarchyPlugin)
+        .add_asset::<Mesh>()
+        .add_asset::<ColorMaterial>()
+        .add_event::<ExplosionEvent>()
+        .insert_resource(Fragmentation {
+            fragments: 3,
+            radius_min: 20.0,
+        })
+        .insert_resource(GameRng::new(Some(1234)))
+        .init_resource::<SharedAssets>()
+        .add_system_to_stage(CoreStage::PreUpdate, collision::mass::update)
+        .add_system(asteroid::fragment)
+        .add_system_to_stage(CoreStage::PostUpdate, wreckage::wreck_with::<Asteroid>)
+        .add_startup_system(
+            move |mut commands: Commands,
+                  mut materials: ResMut<Assets<ColorMaterial>>,
+                  mut meshes: ResMut<Assets<Mesh>>,
+                  game_rng: Res<GameRng>| {
+                asteroid::spawn_one(
+                    &mut commands,
+                    &mut materials,
+                    &mut meshes,
+                    asteroid::outline(&mut game_rng.fork("outline"), radius),
+                    Health(100),
+                    Transform::from_xyz(0.0, 0.0, 0.0),
+                    Velocity(Vec3::new(100.0, 50.0, 0.0)),
+                    AngularVelocity(1.0),
+                );
+            },
+        );
+
+    app.update();
+    for mut health in app
+        .world
+        .query_filtered::<&mut Health, With<Part>>()
+        .iter_mut(&mut app.world)
+    {
+        health.0 = 0;
+    }
+
+    app
+}
+
+// Masses and velocities of the asteroids
+fn asteroids(app: &mut App) -> Vec<(f32, Vec3)> {
+    app.world
+        .query_filtered::<(&Mass, &Velocity), (With<Asteroid>, Without<Part>)>()
+        .iter(&app.world)
+        .map(|(mass, velocity)| (mass.0, velocity.0))
+        .collect()
+}
+
+#[test]
+fn fragment() {
+    let mut app = destroyed(100.0);
+    let [(mass, velocity)]: [(f32, Vec3); 1] = asteroids(&mut app).try_into().unwrap();
+
+    // Fragments get their masses on the update after the one they are spawned on
+    app.update();
+    app.update();
+    let fragments = asteroids(&mut app);
+    assert_eq!(fragments.len(), 3);
+    assert_eq!(app.world.query::<&Wreckage>().iter(&app.world).count(), 0);
+
+    // Fragments are given the area of the asteroid but have lumpy outlines of their own
+    let total: f32 = fragments.iter().map(|(mass, _)| mass).sum();
+    assert!((total / mass - 1.0).abs() < 0.05);
+
+    // Kicks outwards cancel out, up to the differences between the masses of the fragments
+    let momentum: Vec3 = fragments
+        .iter()
+        .map(|(mass, velocity)| *mass * *velocity)
+        .sum();
+    assert!(momentum.distance(mass * velocity) < 0.05 * (mass * velocity).length());
+}
+
+#[test]
+fn fragments_too_small() {
+    // Fragments of an asteroid of radius 30 would have a radius below 20
+    let mut app = destroyed(30.0);
+    app.update();
+    app.update();
+
+    assert!(asteroids(&mut app).is_empty());
+    assert_eq!(app.world.query::<&Wreckage>().iter(&app.world).count(), 1);
+}
@@ -0,0 +1,74 @@
+use asteroids::collision::broad_phase::{Bounds, Grid, CELL_SIZE};
+use bevy::prelude::*;
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg32;
+
+fn random_bounds(rng: &mut Pcg32, count: usize) -> Vec<Bounds> {
+    (0..count)
+        .map(|_| {
+            Bounds::from_center(
+                Vec2::new(
+                    rng.gen_range(-2000.0..2000.0),
+                    rng.gen_range(-2000.0..2000.0),
+                ),
+                Vec2::new(rng.gen_range(5.0..400.0), rng.gen_range(5.0..400.0)),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn pairs_match_brute_force() {
+    let mut rng = Pcg32::seed_from_u64(1234);
+    let bounds = random_bounds(&mut rng, 300);
+    let mut grid = Grid::new(CELL_SIZE);
+    for b in &bounds {
+        grid.insert(*b);
+    }
+
+    let mut expected = Vec::new();
+    for i in 0..bounds.len() {
+        for j in i + 1..bounds.len() {
+            if bounds[i].intersects(bounds[j]) {
+                expected.push((i, j));
+            }
+        }
+    }
+
+    assert_eq!(grid.len(), bounds.len());
+    assert_eq!(grid.pairs(), expected);
+}
+
+#[test]
+fn query_matches_brute_force() {
+    let mut rng = Pcg32::seed_from_u64(5678);
+    let bounds = random_bounds(&mut rng, 200);
+    let mut grid = Grid::new(CELL_SIZE);
+    for b in &bounds {
+        grid.insert(*b);
+    }
+
+    for query in random_bounds(&mut rng, 50) {
+        let expected: Vec<usize> = (0..bounds.len())
+            .filter(|&i| bounds[i].intersects(query))
+            .collect();
+        assert_eq!(grid.query(query), expected);
+    }
+}
+
+#[test]
+fn bodies_over_several_cells_are_paired_once() {
+    let mut grid = Grid::new(CELL_SIZE);
+    grid.insert(Bounds::from_center(Vec2::ZERO, Vec2::splat(CELL_SIZE)));
+    grid.insert(Bounds::from_center(
+        Vec2::splat(10.0),
+        Vec2::splat(CELL_SIZE),
+    ));
+    grid.insert(Bounds::from_center(Vec2::splat(4.0 * CELL_SIZE), Vec2::ONE));
+
+    assert_eq!(grid.pairs(), vec![(0, 1)]);
+    assert_eq!(
+        grid.query(Bounds::from_center(Vec2::ZERO, Vec2::ONE)),
+        vec![0, 1]
+    );
+}
@@ -0,0 +1,56 @@
+use asteroids::*;
+use bevy::{ecs::event::ManualEventReader, prelude::*};
+
+fn contacts(app: &App, reader: &mut ManualEventReader<ContactEvent>) -> Vec<ContactEvent> {
+    reader
+        .iter(app.world.resource::<Events<ContactEvent>>())
+        .copied()
+        .collect()
+}
+
+#[test]
+fn begin_persist_end() {
+    let mut app = App::new();
+    app.add_event::<ContactEvent>()
+        .init_resource::<Cache>()
+        .add_system(asteroids::collision::cache::update);
+    let mut reader = ManualEventReader::default();
+    let [a, b, c] = [(); 3].map(|_| app.world.spawn_empty().id());
+
+    app.world.resource_mut::<Cache>().add(Collision(a, b));
+    app.update();
+    assert_eq!(
+        contacts(&app, &mut reader),
+        [ContactEvent {
+            collision: Collision(a, b),
+            phase: Phase::Begin,
+        }]
+    );
+
+    let mut cache = app.world.resource_mut::<Cache>();
+    assert!(cache.contains(Collision(b, a)));
+    assert!(cache.contains_entity(a));
+    assert!(!cache.contains_entity(c));
+    cache.add(Collision(b, a));
+    cache.add(Collision(a, c));
+    app.update();
+    assert_eq!(
+        contacts(&app, &mut reader),
+        [
+            ContactEvent {
+                collision: Collision(b, a),
+                phase: Phase::Persist,
+            },
+            ContactEvent {
+                collision: Collision(a, c),
+                phase: Phase::Begin,
+            },
+        ]
+    );
+
+    app.update();
+    let contacts = contacts(&app, &mut reader);
+    assert_eq!(contacts.len(), 2);
+    assert!(contacts.iter().all(|contact| contact.phase == Phase::End));
+    assert!(!app.world.resource::<Cache>().contains_entity(a));
+}
@@ -10,7 +10,7 @@ pub mod collision {
         *,
     };
     use bevy::{
-        app::PluginGroupBuilder, prelude::*, render::mesh::PrimitiveTopology, sprite::Mesh2dHandle,
+        prelude::*, render::mesh::PrimitiveTopology, sprite::Mesh2dHandle, utils::Duration,
     };
     use iyes_loopless::prelude::*;
     use std::f32::consts::PI;
@@ -23,8 +23,20 @@ pub mod collision {
     #[ignore]
     fn asteroids_dimension_1() {
         let mut app = App::new();
-        app.add_plugins(TestPlugins)
-            .add_system(collision::generic::with::<asteroid::Asteroid>);
+        app.add_plugins(
+            HeadlessPlugins
+                .build()
+                .disable::<AsteroidsSimulationPlugin>(),
+        )
+        .init_resource::<Cache>()
+        .add_event::<CollisionEvent>()
+        .add_event::<DamageEvent>()
+        .add_fixed_timestep(Duration::from_secs_f32(TIMESTEP), plugin::FIXED_UPDATE)
+        .add_fixed_timestep_system(
+            plugin::FIXED_UPDATE,
+            0,
+            collision::generic::with::<asteroid::Asteroid>,
+        );
 
         let health = Health(100);
         let radius: f32 = 100.0;
@@ -89,8 +101,20 @@ pub mod collision {
     #[ignore]
     fn asteroids_dimension_2() {
         let mut app = App::new();
-        app.add_plugins(TestPlugins)
-            .add_system(collision::generic::with::<asteroid::Asteroid>);
+        app.add_plugins(
+            HeadlessPlugins
+                .build()
+                .disable::<AsteroidsSimulationPlugin>(),
+        )
+        .init_resource::<Cache>()
+        .add_event::<CollisionEvent>()
+        .add_event::<DamageEvent>()
+        .add_fixed_timestep(Duration::from_secs_f32(TIMESTEP), plugin::FIXED_UPDATE)
+        .add_fixed_timestep_system(
+            plugin::FIXED_UPDATE,
+            0,
+            collision::generic::with::<asteroid::Asteroid>,
+        );
 
         let epsilon: f32 = 0.01;
         let health = Health(100);
@@ -165,12 +189,19 @@ pub mod collision {
         let mut app = App::new();
         app.add_plugins(DefaultPlugins)
             .insert_resource(ClearColor(Color::rgb(0., 0., 0.)))
+            .init_resource::<GameRng>()
             .add_event::<SpacePressedEvent>()
             .add_startup_system(spawn_camera)
             .add_system(bevy::window::close_on_esc)
             .add_system(asteroid::spawn)
             .add_system(pause)
-            .add_system_set(
+            .init_resource::<Cache>()
+            .add_event::<CollisionEvent>()
+            .add_event::<DamageEvent>()
+            .add_fixed_timestep(Duration::from_secs_f32(TIMESTEP), plugin::FIXED_UPDATE)
+            .add_fixed_timestep_system_set(
+                plugin::FIXED_UPDATE,
+                0,
                 ConditionSet::new()
                     .run_on_event::<SpacePressedEvent>()
                     .label("Movement")
@@ -178,7 +209,9 @@ pub mod collision {
                     .with_system(move_spaceship)
                     .into(),
             )
-            .add_system(
+            .add_fixed_timestep_system(
+                plugin::FIXED_UPDATE,
+                0,
                 collision::generic::among::<asteroid::Asteroid, spaceship::Spaceship>
                     // .run_on_event::<SpacePressedEvent>()
                     .after("Movement"),
@@ -447,35 +480,4 @@ pub mod collision {
     fn spawn_camera(mut commands: Commands) {
         commands.spawn(Camera2dBundle::default());
     }
-
-    struct TestPlugins;
-
-    impl PluginGroup for TestPlugins {
-        fn build(self) -> PluginGroupBuilder {
-            PluginGroupBuilder::start::<Self>()
-                // .add(bevy::log::LogPlugin::default())
-                .add(bevy::core::CorePlugin::default())
-                .add(bevy::time::TimePlugin::default())
-                // .add(bevy::transform::TransformPlugin::default())
-                // .add(bevy::hierarchy::HierarchyPlugin::default())
-                // .add(bevy::diagnostic::DiagnosticsPlugin::default())
-                // .add(bevy::input::InputPlugin::default())
-                .add(bevy::window::WindowPlugin::default())
-                .add(bevy::asset::AssetPlugin::default())
-                // .add(bevy::asset::debug_asset_server::DebugAssetServerPlugin::default())
-                // .add(bevy::scene::ScenePlugin::default())
-                // .add(bevy::winit::WinitPlugin::default())
-                .add(bevy::render::RenderPlugin::default())
-                .add(bevy::render::texture::ImagePlugin::default())
-                .add(bevy::core_pipeline::CorePipelinePlugin::default())
-                .add(bevy::sprite::SpritePlugin::default())
-            // .add(bevy::text::TextPlugin::default())
-            // .add(bevy::ui::UiPlugin::default())
-            // .add(bevy::pbr::PbrPlugin::default())
-            // .add(bevy::gltf::GltfPlugin::default())
-            // .add(bevy::audio::AudioPlugin::default())
-            // .add(bevy::gilrs::GilrsPlugin::default())
-            // .add(bevy::animation::AnimationPlugin::default())
-        }
-    }
 }
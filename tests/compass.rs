@@ -0,0 +1,47 @@
+use asteroids::compass;
+use bevy::prelude::*;
+
+#[test]
+fn next_waypoint() {
+    let waypoints = [
+        Entity::from_raw(4),
+        Entity::from_raw(2),
+        Entity::from_raw(7),
+    ];
+    assert_eq!(compass::next_waypoint(None, &waypoints), Some(waypoints[0]));
+    assert_eq!(
+        compass::next_waypoint(Some(waypoints[0]), &waypoints),
+        Some(waypoints[1])
+    );
+    assert_eq!(
+        compass::next_waypoint(Some(waypoints[2]), &waypoints),
+        Some(waypoints[0])
+    );
+    // Back to the first waypoint when the active one is gone
+    assert_eq!(
+        compass::next_waypoint(Some(Entity::from_raw(3)), &waypoints),
+        Some(waypoints[0])
+    );
+    assert_eq!(compass::next_waypoint(Some(waypoints[1]), &[]), None);
+}
+
+#[test]
+fn edge_point() {
+    let half_extents = Vec2::new(600.0, 300.0);
+    assert_eq!(
+        compass::edge_point(Vec2::new(500.0, -200.0), half_extents),
+        None
+    );
+    assert_eq!(
+        compass::edge_point(Vec2::new(1200.0, 0.0), half_extents),
+        Some(Vec2::new(600.0, 0.0))
+    );
+    assert_eq!(
+        compass::edge_point(Vec2::new(0.0, -900.0), half_extents),
+        Some(Vec2::new(0.0, -300.0))
+    );
+    assert_eq!(
+        compass::edge_point(Vec2::new(-1200.0, 1200.0), half_extents),
+        Some(Vec2::new(-300.0, 300.0))
+    );
+}
@@ -0,0 +1,39 @@
+use asteroids::{collision::damages, *};
+
+#[test]
+fn mitigate() {
+    assert_eq!(
+        damages::mitigate(10.0, DamageKind::Energy, 1.0, None, None),
+        10
+    );
+
+    // Resistances only apply to their own kind
+    let resistances = Resistances {
+        kinetic: 0.5,
+        ..Default::default()
+    };
+    assert_eq!(
+        damages::mitigate(10.0, DamageKind::Kinetic, 1.0, Some(&resistances), None),
+        5
+    );
+    assert_eq!(
+        damages::mitigate(10.0, DamageKind::Energy, 1.0, Some(&resistances), None),
+        10
+    );
+
+    // Armor applies after resistances and critical hits
+    assert_eq!(
+        damages::mitigate(
+            10.0,
+            DamageKind::Kinetic,
+            2.0,
+            Some(&resistances),
+            Some(&Armor(3))
+        ),
+        7
+    );
+    assert_eq!(
+        damages::mitigate(10.0, DamageKind::Explosive, 1.0, None, Some(&Armor(20))),
+        0
+    );
+}
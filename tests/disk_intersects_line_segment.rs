@@ -28,3 +28,12 @@ fn intersect_disk_and_line_segment() {
     assert!(detection::disk_intersects_line_segment(M, 3.0, O, P).is_none());
     assert!(detection::disk_intersects_line_segment(M, 3.0, I, J).is_some());
 }
+
+#[test]
+fn center_on_line_segment() {
+    let contact = detection::disk_intersects_line_segment(C, 2.0, G, H).unwrap();
+    assert!(contact.normal.is_finite());
+    let contact = detection::disk_intersects_line_segment((G + H) / 2.0, 2.0, G, H).unwrap();
+    assert_eq!(contact.normal, Vec2::X);
+    assert_eq!(contact.depth, 2.0);
+}
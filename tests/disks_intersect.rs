@@ -0,0 +1,64 @@
+use asteroids::*;
+use bevy::prelude::*;
+
+fn disk(radius: f32) -> Collider {
+    Collider {
+        aabb: Aabb {
+            hw: radius,
+            hh: radius,
+        },
+        topology: Topology::Disk { radius },
+    }
+}
+
+#[test]
+fn disks_intersect() {
+    let contact = detection::intersection(
+        Transform::from_xyz(3.0, 0.0, 0.0),
+        Transform::IDENTITY,
+        &disk(2.0),
+        &disk(2.0),
+        None,
+    )
+    .unwrap();
+    assert_eq!(contact.normal, Vec2::X);
+    assert_eq!(contact.depth, 1.0);
+    assert!(detection::intersection(
+        Transform::from_xyz(5.0, 0.0, 0.0),
+        Transform::IDENTITY,
+        &disk(2.0),
+        &disk(2.0),
+        None,
+    )
+    .is_none());
+}
+
+#[test]
+fn same_center() {
+    // Pulled apart along x rather than along a normal of NaNs
+    let contact = detection::intersection(
+        Transform::from_xyz(1.0, 1.0, 0.0),
+        Transform::from_xyz(1.0, 1.0, 0.0),
+        &disk(2.0),
+        &disk(3.0),
+        None,
+    )
+    .unwrap();
+    assert_eq!(contact.normal, Vec2::X);
+    assert_eq!(contact.depth, 5.0);
+
+    let point = Collider {
+        aabb: Aabb { hw: 0.0, hh: 0.0 },
+        topology: Topology::Point,
+    };
+    let contact = detection::intersection(
+        Transform::IDENTITY,
+        Transform::IDENTITY,
+        &point,
+        &disk(2.0),
+        None,
+    )
+    .unwrap();
+    assert_eq!(contact.normal, Vec2::X);
+    assert_eq!(contact.depth, 2.0);
+}
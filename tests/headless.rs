@@ -0,0 +1,77 @@
+use asteroids::*;
+use bevy::prelude::*;
+use iyes_loopless::prelude::*;
+
+#[test]
+fn run_headless() {
+    let mut app = App::new();
+    app.add_plugins(HeadlessPlugins);
+
+    // The first update sets up the game, the second one enters the game
+    app.update();
+    app.update();
+
+    assert_eq!(
+        app.world.resource::<CurrentState<GameState>>().0,
+        GameState::InGame
+    );
+    assert_eq!(
+        app.world
+            .query_filtered::<Entity, (With<Spaceship>, Without<Part>)>()
+            .iter(&app.world)
+            .count(),
+        1
+    );
+    assert_eq!(
+        app.world
+            .query_filtered::<Entity, (With<Boss>, Without<Part>)>()
+            .iter(&app.world)
+            .count(),
+        1
+    );
+
+    for _ in 0..100 {
+        app.update();
+    }
+
+    assert_eq!(
+        app.world.resource::<CurrentState<GameState>>().0,
+        GameState::InGame
+    );
+}
+
+#[test]
+fn game_over() {
+    let mut app = App::new();
+    app.add_plugins(HeadlessPlugins);
+    app.update();
+    app.update();
+
+    // Losing the spaceship fails the mission
+    let spaceship = app
+        .world
+        .query_filtered::<Entity, (With<Spaceship>, Without<Part>)>()
+        .single(&app.world);
+    app.world.entity_mut(spaceship).despawn_recursive();
+    app.update();
+    app.update();
+
+    assert_eq!(
+        app.world.resource::<CurrentState<GameState>>().0,
+        GameState::GameOver
+    );
+
+    // The world goes on
+    let mut query = app
+        .world
+        .query_filtered::<&Transform, (With<Boss>, Without<Part>)>();
+    let transform = *query.single(&app.world);
+    for _ in 0..10 {
+        app.update();
+    }
+    assert_ne!(*query.single(&app.world), transform);
+    assert_eq!(
+        app.world.resource::<CurrentState<GameState>>().0,
+        GameState::GameOver
+    );
+}
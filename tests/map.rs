@@ -1,16 +1,22 @@
 use asteroids::*;
-use bevy::app::PluginGroupBuilder;
 use bevy::prelude::*;
 
 #[test]
 fn count_stars() {
     let mut app = App::new();
-    app.add_plugins(TestPlugins)
-        .add_event::<StarsEvent>()
-        .add_startup_system(camera::spawn)
-        .add_startup_system(map::spawn)
-        .add_system(map::update)
-        .add_system(star::spawn.after(map::update));
+    app.add_plugins(
+        HeadlessPlugins
+            .build()
+            .disable::<AsteroidsSimulationPlugin>(),
+    )
+    .init_resource::<GameRng>()
+    .init_resource::<SharedAssets>()
+    .add_event::<StarsEvent>()
+    .add_event::<RestoreEvent>()
+    .add_startup_system(camera::spawn)
+    .add_startup_system(map::spawn)
+    .add_system(map::update)
+    .add_system(star::spawn.after(map::update));
 
     app.update();
 
@@ -18,6 +24,10 @@ fn count_stars() {
         app.world.query::<&star::Star>().iter(&app.world).count(),
         9 * star::STARS_PER_SECTOR
     );
+    // Stars of the same size and color share their mesh and material
+    let shared_assets = app.world.resource::<SharedAssets>();
+    assert!(shared_assets.circles() < star::STARS_PER_SECTOR);
+    assert!(shared_assets.materials() < star::STARS_PER_SECTOR);
 
     // Move camera to sector (1, 1)
     let (mut c_transform, _) = app
@@ -65,33 +75,168 @@ fn count_stars() {
     );
 }
 
-struct TestPlugins;
-
-impl PluginGroup for TestPlugins {
-    fn build(self) -> PluginGroupBuilder {
-        PluginGroupBuilder::start::<Self>()
-            // .add(bevy::log::LogPlugin::default())
-            .add(bevy::core::CorePlugin::default())
-            .add(bevy::time::TimePlugin::default())
-            // .add(bevy::transform::TransformPlugin::default())
-            // .add(bevy::hierarchy::HierarchyPlugin::default())
-            // .add(bevy::diagnostic::DiagnosticsPlugin::default())
-            // .add(bevy::input::InputPlugin::default())
-            .add(bevy::window::WindowPlugin::default())
-            .add(bevy::asset::AssetPlugin::default())
-            // .add(bevy::asset::debug_asset_server::DebugAssetServerPlugin::default())
-            // .add(bevy::scene::ScenePlugin::default())
-            // .add(bevy::winit::WinitPlugin::default())
-            .add(bevy::render::RenderPlugin::default())
-            .add(bevy::render::texture::ImagePlugin::default())
-            .add(bevy::core_pipeline::CorePipelinePlugin::default())
-            .add(bevy::sprite::SpritePlugin::default())
-        // .add(bevy::text::TextPlugin::default())
-        // .add(bevy::ui::UiPlugin::default())
-        // .add(bevy::pbr::PbrPlugin::default())
-        // .add(bevy::gltf::GltfPlugin::default())
-        // .add(bevy::audio::AudioPlugin::default())
-        // .add(bevy::gilrs::GilrsPlugin::default())
-        // .add(bevy::animation::AnimationPlugin::default())
+#[test]
+fn sector_features() {
+    let sector = map::Sector::new(0, 0, 1234);
+    assert_eq!(sector.kind, map::Sector::new(5, -3, 1234).kind);
+    assert_eq!(sector.heading, map::Sector::new(5, -3, 1234).heading);
+
+    let kinds: Vec<map::SectorKind> = (0..100)
+        .map(|seed| map::Sector::new(0, 0, seed).kind)
+        .collect();
+    for kind in [
+        map::SectorKind::Empty,
+        map::SectorKind::Field,
+        map::SectorKind::Belt,
+    ] {
+        assert!(kinds.contains(&kind));
+    }
+    assert_eq!(map::SectorKind::Empty.density(), 0.0);
+}
+
+fn spawn_asteroid(
+    mut commands: Commands,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    let game_rng = GameRng::new(Some(1234));
+    asteroid::spawn_one(
+        &mut commands,
+        &mut materials,
+        &mut meshes,
+        asteroid::outline(&mut game_rng.fork("outline"), 50.0),
+        Health(7),
+        Transform::from_xyz(-0.5 * WINDOW_WIDTH, 0.5 * WINDOW_HEIGHT, WINDOW_Z)
+            .with_rotation(Quat::from_rotation_z(0.7)),
+        Velocity(Vec3::new(10.0, -5.0, 0.0)),
+        AngularVelocity(1.5),
+    );
+}
+
+fn move_camera(app: &mut App, x: f32, y: f32) {
+    let (mut c_transform, _) = app
+        .world
+        .query::<(&mut Transform, With<Camera>)>()
+        .single_mut(&mut app.world);
+    c_transform.translation.x = x;
+    c_transform.translation.y = y;
+}
+
+#[test]
+fn dormant_asteroid() {
+    let mut app = App::new();
+    app.add_plugins(
+        HeadlessPlugins
+            .build()
+            .disable::<AsteroidsSimulationPlugin>(),
+    )
+    .init_resource::<GameRng>()
+    .add_event::<StarsEvent>()
+    .add_event::<RestoreEvent>()
+    .add_startup_system(camera::spawn)
+    .add_startup_system(map::spawn)
+    .add_startup_system(spawn_asteroid)
+    .add_system(map::update)
+    .add_system(dormant::store.after(map::update))
+    .add_system(dormant::restore.after(map::update));
+
+    let asteroids = |app: &mut App| {
+        app.world
+            .query_filtered::<(&AngularVelocity, &Children, &Transform, &Velocity), (With<Asteroid>, Without<Part>)>()
+            .iter(&app.world)
+            .map(|(angular_velocity, children, transform, velocity)| {
+                (children.len(), *transform, velocity.0, angular_velocity.0)
+            })
+            .collect::<Vec<_>>()
+    };
+
+    app.update();
+    let before = asteroids(&mut app);
+    assert_eq!(before.len(), 1);
+    assert_eq!(
+        before[0].1.translation,
+        Vec3::new(-0.5 * WINDOW_WIDTH, 0.5 * WINDOW_HEIGHT, WINDOW_Z)
+    );
+
+    // Move camera to sector (1, 0), out of range of the asteroid
+    move_camera(&mut app, 1.5 * WINDOW_WIDTH, 0.5 * WINDOW_HEIGHT);
+    app.update();
+    assert!(asteroids(&mut app).is_empty());
+
+    // Move camera back to sector (0, 0)
+    move_camera(&mut app, 0.5 * WINDOW_WIDTH, 0.5 * WINDOW_HEIGHT);
+    app.update();
+    let after = asteroids(&mut app);
+    assert_eq!(after.len(), 1);
+    let ((parts_before, t_before, v_before, w_before), (parts_after, t_after, v_after, w_after)) =
+        (before[0], after[0]);
+    assert_eq!(parts_after, parts_before);
+    assert!(t_after.translation.abs_diff_eq(t_before.translation, 1e-3));
+    assert!(t_after.rotation.abs_diff_eq(t_before.rotation, 1e-5));
+    assert_eq!(v_after, v_before);
+    assert_eq!(w_after, w_before);
+    let health = app
+        .world
+        .query_filtered::<&Health, (With<Asteroid>, With<Part>)>()
+        .single(&app.world);
+    assert_eq!(health.0, 7);
+}
+
+#[test]
+fn world_seed() {
+    let world_seed = WorldSeed(1234);
+    assert_eq!(
+        world_seed.of_sector(3, -2),
+        WorldSeed(1234).of_sector(3, -2)
+    );
+    assert_ne!(world_seed.of_sector(3, -2), world_seed.of_sector(-2, 3));
+    assert_ne!(world_seed.of_sector(3, -2), world_seed.of_sector(3, -1));
+    assert_ne!(
+        world_seed.of_sector(3, -2),
+        WorldSeed(1235).of_sector(3, -2)
+    );
+}
+
+#[test]
+fn parallax() {
+    let mut app = App::new();
+    app.add_plugins(
+        HeadlessPlugins
+            .build()
+            .disable::<AsteroidsSimulationPlugin>(),
+    )
+    .init_resource::<GameRng>()
+    .init_resource::<SharedAssets>()
+    .add_event::<StarsEvent>()
+    .add_event::<RestoreEvent>()
+    .add_startup_system(camera::spawn)
+    .add_startup_system(map::spawn)
+    .add_system(map::update)
+    .add_system(star::spawn.after(map::update))
+    .add_system_to_stage(CoreStage::PostUpdate, star::parallax);
+
+    app.update();
+    move_camera(&mut app, 0.5 * WINDOW_WIDTH + 100.0, 0.5 * WINDOW_HEIGHT);
+    app.update();
+
+    // Layers of the sector of the camera follow it all the more as they are far
+    let mut shifts: Vec<(f32, f32)> = app
+        .world
+        .query::<(&star::StarLayer, &Parent, &Transform)>()
+        .iter(&app.world)
+        .filter(|(_, parent, _)| {
+            app.world
+                .get::<Transform>(***parent)
+                .is_some_and(|transform| {
+                    transform.translation.truncate()
+                        == Vec2::new(0.5 * WINDOW_WIDTH, 0.5 * WINDOW_HEIGHT)
+                })
+        })
+        .map(|(layer, _, transform)| (layer.parallax, transform.translation.x))
+        .collect();
+    shifts.sort_by(|(p1, _), (p2, _)| p1.total_cmp(p2));
+    assert_eq!(shifts.len(), 3);
+    for (parallax, shift) in shifts {
+        assert!((shift - (1.0 - parallax) * 100.0).abs() < 1e-3);
     }
 }
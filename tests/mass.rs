@@ -0,0 +1,42 @@
+use asteroids::collision::{detection::triangle::TriangleXY, mass::MassProperties};
+use bevy::prelude::*;
+use std::f32::consts::PI;
+
+const EPSILON: f32 = 1e-5;
+
+#[test]
+fn square() {
+    // Unit square split along its diagonal, one triangle CCW and the other CW
+    let square: MassProperties = [
+        TriangleXY(Vec2::ZERO, Vec2::X, Vec2::ONE),
+        TriangleXY(Vec2::ZERO, Vec2::Y, Vec2::ONE),
+    ]
+    .into_iter()
+    .map(|triangle| MassProperties::of_triangle(triangle, 2.0))
+    .sum();
+
+    assert!((square.mass - 2.0).abs() < EPSILON);
+    assert!(square.centroid.distance(Vec2::splat(0.5)) < EPSILON);
+    // m(w² + h²) / 12
+    assert!((square.moment_of_inertia - 2.0 * 2.0 / 12.0).abs() < EPSILON);
+}
+
+#[test]
+fn disks() {
+    let disk = MassProperties::of_disk(Vec2::ZERO, 2.0, 1.0);
+    assert_eq!(disk.mass, 4.0 * PI);
+    assert_eq!(disk.moment_of_inertia, 8.0 * PI);
+
+    // Two disks apart rotate around the middle of their centers,
+    // each one contributing its mass times the squared distance to it
+    let moved = disk.transformed(Transform::from_xyz(10.0, 0.0, 0.0));
+    assert_eq!(moved.centroid, Vec2::new(10.0, 0.0));
+    let pair: MassProperties = [disk, moved].into_iter().sum();
+    assert!(pair.centroid.distance(Vec2::new(5.0, 0.0)) < EPSILON);
+    assert!((pair.moment_of_inertia - 2.0 * (8.0 * PI + 4.0 * PI * 25.0)).abs() < 1e-3);
+
+    assert_eq!(
+        std::iter::empty::<MassProperties>().sum::<MassProperties>(),
+        MassProperties::default()
+    );
+}
@@ -0,0 +1,182 @@
+use asteroids::{
+    mission::{MissionError, Report, Status},
+    *,
+};
+use bevy::prelude::*;
+
+fn report(spaceship_sector: (isize, isize)) -> Report {
+    Report {
+        delta: 0.5,
+        spaceship_sector: Some(spaceship_sector),
+        ..default()
+    }
+}
+
+#[test]
+fn objectives() {
+    let mut mission = Mission::new(&[
+        Goal::DestroyAsteroids(3),
+        Goal::Survive(1.0),
+        Goal::ReachSector(2, -1),
+    ])
+    .unwrap();
+
+    mission.advance(&Report {
+        asteroids_destroyed: 2,
+        ..report((0, 0))
+    });
+    assert_eq!(mission.objectives[0].status, Status::InProgress);
+    assert_eq!(mission.status, Status::InProgress);
+
+    mission.advance(&Report {
+        asteroids_destroyed: 1,
+        ..report((2, -1))
+    });
+    assert_eq!(mission.objectives[0].status, Status::Completed);
+    assert_eq!(mission.objectives[1].status, Status::Completed);
+    assert_eq!(mission.objectives[2].status, Status::Completed);
+    assert_eq!(mission.status, Status::Completed);
+
+    // Nothing changes once the mission is over
+    mission.advance(&Report {
+        spaceship_sector: None,
+        ..default()
+    });
+    assert_eq!(mission.status, Status::Completed);
+
+    mission.reset();
+    assert_eq!(mission.status, Status::InProgress);
+    assert_eq!(mission.objectives[0].asteroids_destroyed, 0);
+}
+
+#[test]
+fn failures() {
+    let mut mission = Mission::default();
+    mission.advance(&Report {
+        spaceship_sector: None,
+        ..default()
+    });
+    assert_eq!(mission.status, Status::Failed);
+
+    let mut mission = Mission::new(&[Goal::Escort {
+        freighters: 2,
+        i: 1,
+        j: 1,
+    }])
+    .unwrap();
+    mission.advance(&Report {
+        freighter_sectors: vec![(1, 1), (0, 1)],
+        ..report((1, 1))
+    });
+    assert_eq!(mission.status, Status::InProgress);
+    mission.advance(&Report {
+        freighter_sectors: vec![(1, 1), (1, 1)],
+        freighter_destroyed: true,
+        ..report((1, 1))
+    });
+    assert_eq!(mission.status, Status::Failed);
+}
+
+#[test]
+fn invalid_missions() {
+    assert_eq!(Mission::new(&[]).unwrap_err(), MissionError::NoObjective);
+    assert_eq!(
+        Mission::new(&[
+            Goal::DestroyBoss,
+            Goal::Escort {
+                freighters: 0,
+                i: 1,
+                j: 1,
+            }
+        ])
+        .unwrap_err(),
+        MissionError::EmptyEscort
+    );
+}
+
+#[test]
+fn parse() {
+    let mission = Mission::parse(
+        "# Comment\n\
+         destroy_asteroids 3\n\
+         \n\
+         survive 1.5\n\
+         reach_sector 2 -1\n\
+         \x20 destroy_boss\n\
+         escort 2 -3 4\n",
+    )
+    .unwrap();
+    let goals: Vec<Goal> = mission.objectives.iter().map(|o| o.goal).collect();
+    assert_eq!(
+        goals,
+        [
+            Goal::DestroyAsteroids(3),
+            Goal::Survive(1.5),
+            Goal::ReachSector(2, -1),
+            Goal::DestroyBoss,
+            Goal::Escort {
+                freighters: 2,
+                i: -3,
+                j: 4,
+            },
+        ]
+    );
+
+    assert_eq!(
+        Mission::parse("destroy_boss\nreach_sector 1\n").unwrap_err(),
+        MissionError::InvalidLine(2, "unknown goal reach_sector 1".to_string())
+    );
+    assert_eq!(
+        Mission::parse("survive soon").unwrap_err(),
+        MissionError::InvalidLine(1, "invalid argument soon".to_string())
+    );
+    assert_eq!(
+        Mission::parse("# Nothing to do\n").unwrap_err(),
+        MissionError::NoObjective
+    );
+    assert_eq!(
+        Mission::parse("escort 0 1 1").unwrap_err(),
+        MissionError::EmptyEscort
+    );
+}
+
+#[test]
+fn load() {
+    let mission = Mission::load("assets/missions/escort.txt").unwrap();
+    assert_eq!(mission.objectives.len(), 3);
+    assert_eq!(
+        Mission::load("assets/missions/missing.txt")
+            .unwrap_err()
+            .kind(),
+        std::io::ErrorKind::NotFound
+    );
+}
+
+#[test]
+fn objective_text() {
+    assert_eq!(
+        objective::text(&Mission::default()),
+        "Mission objective: Eliminate the target"
+    );
+    assert_eq!(
+        objective::text(&Mission::new(&[Goal::Survive(60.0), Goal::ReachSector(3, -2)]).unwrap()),
+        "Mission objectives:\nSurvive 60 seconds\nReach sector (3, -2)"
+    );
+}
+
+#[test]
+fn survive_headless() {
+    let mut app = App::new();
+    app.insert_resource(Mission::new(&[Goal::Survive(0.5)]).unwrap())
+        .add_plugins(HeadlessPlugins);
+
+    // The first update sets up the game, the second one enters the game
+    app.update();
+    app.update();
+
+    for _ in 0..(0.5 / TIMESTEP) as usize + 2 {
+        app.update();
+    }
+
+    assert_eq!(app.world.resource::<Mission>().status, Status::Completed);
+}
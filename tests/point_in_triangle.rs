@@ -38,3 +38,33 @@ fn points_in_triangles() {
     assert!(detection::point_in_triangle(I, [J, K, M]).is_none());
     assert!(detection::point_in_triangle(D, [J, K, M]).is_none());
 }
+
+#[test]
+fn points_in_cw_triangles() {
+    // Same contacts whichever way the triangles are wound
+    for p in [D, E, F, G, H, I, J, K, L, M, N, O] {
+        assert_eq!(
+            detection::point_in_triangle(p, [A, C, B]),
+            detection::point_in_triangle(p, [A, B, C])
+        );
+    }
+    for p in [A, N, H, F, I, D] {
+        assert_eq!(
+            detection::point_in_triangle(p, [J, M, K]),
+            detection::point_in_triangle(p, [J, K, M])
+        );
+    }
+}
+
+#[test]
+fn disks_intersect_cw_triangles() {
+    let contact = detection::disk_intersects_triangle(E, 1.0, [A, C, B]).unwrap();
+    assert_eq!(
+        Some(contact),
+        detection::disk_intersects_triangle(E, 1.0, [A, B, C])
+    );
+    // The normal points from the triangle towards the center of the disk, outside of it
+    assert!(contact.normal.dot(E - contact.point) > 0.0);
+    assert!(detection::disk_intersects_triangle(O, 0.5, [A, C, B]).is_some());
+    assert!(detection::disk_intersects_triangle(L, 1.0, [A, C, B]).is_none());
+}
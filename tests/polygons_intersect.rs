@@ -0,0 +1,116 @@
+use asteroids::collision::detection::polygon;
+use bevy::prelude::*;
+
+// Square of side 4 centered at the origin and a pentagon overlapping its right side
+const SQUARE: [Vec2; 4] = [
+    Vec2 { x: -2.0, y: -2.0 },
+    Vec2 { x: 2.0, y: -2.0 },
+    Vec2 { x: 2.0, y: 2.0 },
+    Vec2 { x: -2.0, y: 2.0 },
+];
+const PENTAGON: [Vec2; 5] = [
+    Vec2 { x: 1.5, y: -1.0 },
+    Vec2 { x: 4.0, y: -1.5 },
+    Vec2 { x: 5.0, y: 0.0 },
+    Vec2 { x: 4.0, y: 1.5 },
+    Vec2 { x: 1.5, y: 1.0 },
+];
+
+fn translated(polygon: &[Vec2], translation: Vec2) -> Vec<Vec2> {
+    polygon.iter().map(|vertex| *vertex + translation).collect()
+}
+
+#[test]
+fn intersect_polygons() {
+    let contact = polygon::polygons_intersect(&PENTAGON, &SQUARE).unwrap();
+    assert_eq!(contact.normal, Vec2::X);
+    assert!((contact.depth - 0.5).abs() < 1e-6);
+
+    // Normal from the second polygon towards the first one
+    let contact = polygon::polygons_intersect(&SQUARE, &PENTAGON).unwrap();
+    assert_eq!(contact.normal, Vec2::NEG_X);
+    assert!((contact.depth - 0.5).abs() < 1e-6);
+}
+
+#[test]
+fn do_not_intersect_polygons() {
+    let pentagon = translated(&PENTAGON, Vec2::new(0.6, 0.0));
+    assert!(polygon::polygons_intersect(&pentagon, &SQUARE).is_none());
+    assert!(polygon::polygons_intersect(&SQUARE, &pentagon).is_none());
+
+    // Separated along a diagonal only
+    let square = translated(&SQUARE, Vec2::new(4.1, 4.1));
+    assert!(polygon::polygons_intersect(&square, &SQUARE).is_none());
+}
+
+#[test]
+fn point_in_polygon() {
+    let contact = polygon::point_in_polygon(Vec2::new(0.0, 1.5), &SQUARE).unwrap();
+    assert_eq!(contact.normal, Vec2::Y);
+    assert!((contact.depth - 0.5).abs() < 1e-6);
+
+    assert!(polygon::point_in_polygon(Vec2::new(0.0, 2.5), &SQUARE).is_none());
+}
+
+#[test]
+fn disk_intersects_polygon() {
+    // Disk centered outside, close to a corner
+    let contact = polygon::disk_intersects_polygon(Vec2::new(3.0, 3.0), 1.5, &SQUARE).unwrap();
+    assert_eq!(contact.point, Vec2::new(2.0, 2.0));
+    assert!((contact.depth - (1.5 - 2.0_f32.sqrt())).abs() < 1e-6);
+    assert!(polygon::disk_intersects_polygon(Vec2::new(3.0, 3.0), 1.4, &SQUARE).is_none());
+
+    // Disk centered inside
+    let contact = polygon::disk_intersects_polygon(Vec2::new(-1.5, 0.0), 1.0, &SQUARE).unwrap();
+    assert_eq!(contact.normal, Vec2::NEG_X);
+    assert!((contact.depth - 1.5).abs() < 1e-6);
+}
+
+#[test]
+fn triangles() {
+    let area: f32 = polygon::triangles(&PENTAGON)
+        .iter()
+        .map(|triangle| triangle.area())
+        .sum();
+    assert_eq!(polygon::triangles(&PENTAGON).len(), 3);
+    assert!((area - 7.75).abs() < 1e-5);
+}
+
+#[test]
+fn edge_contact_has_two_points() {
+    let square = translated(&SQUARE, Vec2::new(1.0, 3.9));
+    let contact = polygon::polygons_intersect(&square, &SQUARE).unwrap();
+    assert_eq!(contact.normal, Vec2::Y);
+    assert!((contact.depth - 0.1).abs() < 1e-5);
+    assert!((contact.point.x - -1.0).abs() < 1e-5);
+    assert!((contact.point2.unwrap().x - 2.0).abs() < 1e-5);
+    assert!((contact.center().x - 0.5).abs() < 1e-5);
+
+    // Corner against edge
+    let diamond = [
+        Vec2::new(0.0, 1.5),
+        Vec2::new(1.0, 2.5),
+        Vec2::new(0.0, 3.5),
+        Vec2::new(-1.0, 2.5),
+    ];
+    let contact = polygon::polygons_intersect(&diamond, &SQUARE).unwrap();
+    assert_eq!(contact.point, Vec2::new(0.0, 1.5));
+    assert!(contact.point2.is_none());
+}
+
+#[test]
+fn ccw_triangle() {
+    // Triangle overlapping the right side of the square, given in CW order
+    let cw = [
+        Vec2::new(1.5, 0.0),
+        Vec2::new(4.0, 1.0),
+        Vec2::new(4.0, -1.0),
+    ];
+    let ccw = polygon::ccw(cw);
+    assert_eq!(ccw, [cw[0], cw[2], cw[1]]);
+    assert_eq!(polygon::ccw(ccw), ccw);
+
+    let contact = polygon::polygons_intersect(&ccw, &SQUARE).unwrap();
+    assert_eq!(contact.normal, Vec2::X);
+    assert!((contact.depth - 0.5).abs() < 1e-6);
+}
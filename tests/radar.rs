@@ -0,0 +1,25 @@
+use asteroids::radar;
+use bevy::prelude::*;
+
+#[test]
+fn clip_to_disk() {
+    // Chord through the center
+    let (a, b) = radar::clip_to_disk(Vec2::new(-20.0, 0.0), Vec2::new(20.0, 0.0), 10.0).unwrap();
+    assert!(a.distance(Vec2::new(-10.0, 0.0)) < 1e-4);
+    assert!(b.distance(Vec2::new(10.0, 0.0)) < 1e-4);
+
+    // Segment starting inside the disk
+    let (a, b) = radar::clip_to_disk(Vec2::new(0.0, 6.0), Vec2::new(20.0, 6.0), 10.0).unwrap();
+    assert_eq!(a, Vec2::new(0.0, 6.0));
+    assert!(b.distance(Vec2::new(8.0, 6.0)) < 1e-4);
+
+    // Segment within the disk
+    assert_eq!(
+        radar::clip_to_disk(Vec2::new(-1.0, 1.0), Vec2::new(1.0, -1.0), 10.0),
+        Some((Vec2::new(-1.0, 1.0), Vec2::new(1.0, -1.0)))
+    );
+
+    // Segments missing the disk, beside it or short of it
+    assert!(radar::clip_to_disk(Vec2::new(-20.0, 11.0), Vec2::new(20.0, 11.0), 10.0).is_none());
+    assert!(radar::clip_to_disk(Vec2::new(12.0, 0.0), Vec2::new(20.0, 0.0), 10.0).is_none());
+}
@@ -0,0 +1,142 @@
+use asteroids::{keyboard_bindings::KeyboardBindings, replay, *};
+use bevy::{
+    input::{keyboard::KeyboardInput, ButtonState},
+    prelude::*,
+};
+
+fn recording() -> Recording {
+    let mut intents = vec![Intent::default(); 10];
+    intents.extend(
+        [Intent {
+            accelerate: true,
+            ..default()
+        }; 300],
+    );
+    intents.push(Intent {
+        rotate_left: true,
+        fire: true,
+        ..default()
+    });
+    intents.extend(
+        [Intent {
+            decelerate: true,
+            rotate_right: true,
+            camera: true,
+            ..default()
+        }; 20],
+    );
+
+    Recording {
+        seed: 1234,
+        timestep: TIMESTEP,
+        intents,
+    }
+}
+
+#[test]
+fn intent_bits() {
+    for bits in 0..1 << 7 {
+        assert_eq!(Intent::from_bits(bits).to_bits(), bits);
+    }
+}
+
+#[test]
+fn write_then_read() {
+    let recording = recording();
+    let mut file = Vec::new();
+    recording.write(&mut file).unwrap();
+
+    // Header of 17 bytes then runs of at most 255 ticks
+    assert_eq!(file.len(), 17 + 2 * 5);
+    assert_eq!(Recording::read(file.as_slice()).unwrap(), recording);
+    assert!(Recording::read(&file[..16]).is_err());
+    assert!(Recording::read(&file[..file.len() - 1]).is_err());
+}
+
+fn replay(recording: Recording) -> Transform {
+    let mut app = App::new();
+    app.add_plugins(HeadlessPlugins)
+        .add_plugin(ReplayPlugin { recording });
+
+    for _ in 0..400 {
+        app.update();
+    }
+
+    *app.world
+        .query_filtered::<&Transform, (With<Spaceship>, Without<Part>)>()
+        .single(&app.world)
+}
+
+#[test]
+fn replay_is_deterministic() {
+    let transform = replay(recording());
+    assert_eq!(transform, replay(recording()));
+    assert_ne!(transform.translation, spaceship::TRANSLATION);
+}
+
+// Translations and rotations of the bodies, sorted since entities differ from one app to the other
+fn bodies(app: &mut App) -> Vec<[f32; 7]> {
+    let mut bodies: Vec<[f32; 7]> = app
+        .world
+        .query_filtered::<&Transform, (With<Velocity>, Without<Parent>)>()
+        .iter(&app.world)
+        .map(|transform| {
+            let [x, y, z] = transform.translation.to_array();
+            let [i, j, k, w] = transform.rotation.to_array();
+            [x, y, z, i, j, k, w]
+        })
+        .collect();
+    bodies.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    bodies
+}
+
+#[test]
+fn replay_matches_record() {
+    const UPDATES: usize = 300;
+
+    let mut app = App::new();
+    app.insert_resource(GameRng::new(Some(5678)))
+        .add_plugins(HeadlessPlugins)
+        .add_plugin(RecordPlugin {
+            path: std::env::temp_dir().join("asteroids_replay_matches_record"),
+        });
+    // Accelerate all along, turning left for a while and firing every 20 ticks.
+    // Keys go through the input events, as the keyboard input system clears
+    // the keys just pressed at the start of each update.
+    let fire = KeyboardBindings::default().fire();
+    for update in 0..UPDATES {
+        let keys: &[(KeyCode, ButtonState)] = match update {
+            0 => &[(KeyCode::Up, ButtonState::Pressed)],
+            100 => &[(KeyCode::Left, ButtonState::Pressed)],
+            150 => &[(KeyCode::Left, ButtonState::Released)],
+            _ => &[],
+        };
+        let fire_state = if update % 20 == 0 {
+            ButtonState::Pressed
+        } else {
+            ButtonState::Released
+        };
+        for &(key_code, state) in keys.iter().chain([&(fire, fire_state)]) {
+            app.world.send_event(KeyboardInput {
+                scan_code: 0,
+                key_code: Some(key_code),
+                state,
+            });
+        }
+        app.update();
+    }
+    let recording = app.world.resource::<replay::Recorder>().recording().clone();
+    assert!(recording.intents.iter().any(|intent| intent.fire));
+    let recorded = bodies(&mut app);
+
+    let mut app = App::new();
+    app.add_plugins(HeadlessPlugins)
+        .add_plugin(ReplayPlugin { recording });
+    for _ in 0..UPDATES {
+        app.update();
+    }
+
+    assert!(recorded.len() > 2);
+    assert_eq!(bodies(&mut app), recorded);
+}
@@ -0,0 +1,82 @@
+use asteroids::{collision::response, *};
+use bevy::prelude::*;
+
+// Two bodies of unit mass and inertia touching at the origin, body 1 on the left
+fn collide(
+    velocity1: Vec3,
+    velocity2: Vec3,
+    material: PhysicsMaterial,
+) -> (Velocity, Velocity, AngularVelocity, AngularVelocity) {
+    let [mut velocity1, mut velocity2] = [Velocity(velocity1), Velocity(velocity2)];
+    let [mut angular_velocity1, mut angular_velocity2] = [AngularVelocity(0.0); 2];
+    response::compute_velocities(
+        &mut velocity1,
+        &mut velocity2,
+        &mut angular_velocity1,
+        &mut angular_velocity2,
+        Transform::from_xyz(-5.0, 0.0, 0.0),
+        Transform::from_xyz(5.0, 0.0, 0.0),
+        Mass(1.0),
+        Mass(1.0),
+        MomentOfInertia(1.0),
+        MomentOfInertia(1.0),
+        material,
+        material,
+        Contact::new(Vec2::ZERO, Vec2::NEG_X, 0.0),
+    );
+
+    (velocity1, velocity2, angular_velocity1, angular_velocity2)
+}
+
+#[test]
+fn restitution() {
+    let elastic = PhysicsMaterial::default();
+    let (velocity1, velocity2, _, _) = collide(Vec3::X, Vec3::NEG_X, elastic);
+    assert_eq!(velocity1.0, Vec3::NEG_X);
+    assert_eq!(velocity2.0, Vec3::X);
+
+    let inelastic = PhysicsMaterial {
+        restitution: 0.0,
+        ..elastic
+    };
+    let (velocity1, velocity2, _, _) = collide(Vec3::X, Vec3::NEG_X, inelastic);
+    assert_eq!(velocity1.0, Vec3::ZERO);
+    assert_eq!(velocity2.0, Vec3::ZERO);
+
+    // The bounciest material wins
+    assert_eq!(inelastic.restitution_with(&elastic), 1.0);
+}
+
+#[test]
+fn friction() {
+    let [velocity1, velocity2] = [Vec3::new(1.0, 1.0, 0.0), Vec3::new(-1.0, -1.0, 0.0)];
+    let slippery = PhysicsMaterial {
+        restitution: 0.0,
+        friction: 0.0,
+        density: 1.0,
+    };
+    let (v1, v2, w1, w2) = collide(velocity1, velocity2, slippery);
+    assert_eq!(v1.0, Vec3::Y);
+    assert_eq!(v2.0, Vec3::NEG_Y);
+    assert_eq!([w1.0, w2.0], [0.0; 2]);
+
+    // Enough friction stops the contact points from sliding and sets the bodies spinning
+    let rough = PhysicsMaterial {
+        friction: 1.0,
+        ..slippery
+    };
+    let (v1, v2, w1, w2) = collide(velocity1, velocity2, rough);
+    let point_velocity1 = v1.0 + w1.0 * Vec3::Z.cross(Vec3::new(5.0, 0.0, 0.0));
+    let point_velocity2 = v2.0 + w2.0 * Vec3::Z.cross(Vec3::new(-5.0, 0.0, 0.0));
+    assert!(point_velocity1.distance(point_velocity2) < 1e-5);
+    assert!(w1.0 < 0.0 && w2.0 < 0.0);
+
+    // Little friction only slows sliding down
+    let smooth = PhysicsMaterial {
+        friction: 0.01,
+        ..slippery
+    };
+    let (v1, v2, _, _) = collide(velocity1, velocity2, smooth);
+    assert!(v1.0.y > 0.0 && v1.0.y < 1.0);
+    assert!(v2.0.y < 0.0 && v2.0.y > -1.0);
+}
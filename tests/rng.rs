@@ -0,0 +1,66 @@
+use asteroids::{rng, *};
+use bevy::prelude::*;
+use rand::Rng;
+
+#[test]
+fn fork() {
+    let game_rng = GameRng::new(Some(1234));
+    let mut a = game_rng.fork("a");
+    let mut b = game_rng.fork("b");
+    let mut a_again = GameRng::new(Some(1234)).fork("a");
+
+    let a: Vec<u32> = (0..10).map(|_| a.gen()).collect();
+    let b: Vec<u32> = (0..10).map(|_| b.gen()).collect();
+    let a_again: Vec<u32> = (0..10).map(|_| a_again.gen()).collect();
+
+    assert_eq!(a, a_again);
+    assert_ne!(a, b);
+}
+
+#[test]
+fn reseed() {
+    let mut fixed = GameRng::new(Some(1234));
+    fixed.reseed();
+    assert_eq!(fixed.seed(), 1234);
+}
+
+fn boss_translation(seed: u64) -> Vec3 {
+    let mut app = App::new();
+    app.insert_resource(GameRng::new(Some(seed)))
+        .add_plugins(HeadlessPlugins);
+    app.update();
+
+    app.world
+        .query_filtered::<&Transform, (With<Boss>, Without<Part>)>()
+        .single(&app.world)
+        .translation
+}
+
+#[test]
+fn same_seed_same_game() {
+    assert_eq!(boss_translation(1234), boss_translation(1234));
+    assert_ne!(boss_translation(1234), boss_translation(4321));
+}
+
+#[test]
+fn stream() {
+    let mut game_rng = GameRng::new(Some(1234));
+    let mut stream = rng::Stream::default();
+    let mut named = 0;
+    let mut draw = |stream: &mut rng::Stream, game_rng: &GameRng| -> u32 {
+        stream
+            .get_with(game_rng, || {
+                named += 1;
+                String::from("a::b")
+            })
+            .gen()
+    };
+
+    // The name is only built when the stream is forked, on first use and after each reseed
+    let first = draw(&mut stream, &game_rng);
+    assert_eq!(first, game_rng.fork("a::b").gen::<u32>());
+    draw(&mut stream, &game_rng);
+    game_rng.reseed();
+    assert_eq!(draw(&mut stream, &game_rng), first);
+    assert_eq!(named, 2);
+}
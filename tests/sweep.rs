@@ -0,0 +1,124 @@
+use asteroids::collision::detection::{self, sweep, Aabb, Collider, Topology};
+use bevy::{
+    ecs::system::SystemState, prelude::*, render::mesh::PrimitiveTopology, sprite::Mesh2dHandle,
+};
+
+// Thin CCW rectangle, 2 units wide
+const WALL: [Vec2; 4] = [
+    Vec2 { x: -1.0, y: -50.0 },
+    Vec2 { x: 1.0, y: -50.0 },
+    Vec2 { x: 1.0, y: 50.0 },
+    Vec2 { x: -1.0, y: 50.0 },
+];
+
+#[test]
+fn point_goes_through_wall() {
+    // Fire at 1200 units/s over a tick at 60 Hz, from one side of the wall to the other
+    let (s, contact) =
+        sweep::disk_against_polygon(Vec2::new(-11.0, 0.0), Vec2::new(20.0, 0.0), 0.0, &WALL)
+            .unwrap();
+    assert!((s - 0.5).abs() < 1e-6);
+    assert_eq!(contact.normal, Vec2::NEG_X);
+    assert_eq!(contact.point, Vec2::new(-1.0, 0.0));
+
+    // Stopping short of the wall or moving away from it
+    assert!(
+        sweep::disk_against_polygon(Vec2::new(-11.0, 0.0), Vec2::new(9.0, 0.0), 0.0, &WALL)
+            .is_none()
+    );
+    assert!(
+        sweep::disk_against_polygon(Vec2::new(11.0, 0.0), Vec2::new(20.0, 0.0), 0.0, &WALL)
+            .is_none()
+    );
+}
+
+#[test]
+fn disk_hits_corner() {
+    let (s, contact) =
+        sweep::disk_against_polygon(Vec2::new(0.0, 55.0), Vec2::new(0.0, -10.0), 4.0, &WALL)
+            .unwrap();
+    assert!((s - 0.1).abs() < 1e-5);
+    assert_eq!(contact.normal, Vec2::Y);
+
+    // Passing by the corner at a distance of 3 units along the diagonal
+    let p = Vec2::new(1.0, 50.0) + 3.0 * Vec2::new(1.0, 1.0).normalize();
+    let d = 20.0 * Vec2::new(1.0, -1.0).normalize();
+    assert!(sweep::disk_against_polygon(p - d / 2.0, d, 2.5, &WALL).is_none());
+    let (_, contact) = sweep::disk_against_polygon(p - d / 2.0, d, 3.5, &WALL).unwrap();
+    assert!((contact.point - Vec2::new(1.0, 50.0)).length() < 1e-4);
+}
+
+#[test]
+fn disks() {
+    let (s, contact) = sweep::disk_against_disk(
+        Vec2::new(-20.0, 0.0),
+        Vec2::new(40.0, 0.0),
+        2.0,
+        Vec2::ZERO,
+        8.0,
+    )
+    .unwrap();
+    assert!((s - 0.25).abs() < 1e-6);
+    assert_eq!(contact.normal, Vec2::NEG_X);
+    assert_eq!(contact.point, Vec2::new(-8.0, 0.0));
+
+    // Already touching
+    assert!(sweep::disk_against_disk(
+        Vec2::new(-9.0, 0.0),
+        Vec2::new(40.0, 0.0),
+        2.0,
+        Vec2::ZERO,
+        8.0
+    )
+    .is_none());
+    // Missing
+    assert!(sweep::disk_against_disk(
+        Vec2::new(-20.0, 11.0),
+        Vec2::new(40.0, 0.0),
+        2.0,
+        Vec2::ZERO,
+        8.0
+    )
+    .is_none());
+}
+
+#[test]
+fn point_goes_through_cw_triangle() {
+    let mut app = App::new();
+    app.add_plugin(bevy::asset::AssetPlugin::default())
+        .add_asset::<Mesh>();
+    let world = &mut app.world;
+
+    // Thin triangle pointing towards x, wound CW as mesh triangles may be
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+    mesh.insert_attribute(
+        Mesh::ATTRIBUTE_POSITION,
+        vec![[-2.0, -20.0, 0.0], [-2.0, 20.0, 0.0], [2.0, 0.0, 0.0]],
+    );
+    let mesh_handle = world.resource_mut::<Assets<Mesh>>().add(mesh);
+    let triangle = Collider {
+        aabb: Aabb { hw: 2.0, hh: 20.0 },
+        topology: Topology::Triangles {
+            mesh_handle: Mesh2dHandle(mesh_handle),
+        },
+    };
+    let point = Collider {
+        aabb: Aabb { hw: 0.0, hh: 0.0 },
+        topology: Topology::Point,
+    };
+
+    // From one side of the triangle to the other over the tick
+    let mut state = SystemState::<Res<Assets<Mesh>>>::new(world);
+    let (s, contact) = detection::swept_intersection(
+        Transform::from_xyz(30.0, 0.0, 0.0),
+        Transform::IDENTITY,
+        &point,
+        &triangle,
+        Vec2::new(40.0, 0.0),
+        Some(state.get(world)),
+    )
+    .unwrap();
+    assert!((s - 0.2).abs() < 1e-6);
+    assert_eq!(contact.normal, Vec2::NEG_X);
+    assert_eq!(contact.point, Vec2::new(-2.0, 0.0));
+}
@@ -49,7 +49,9 @@ fn intersect_triangles() {
     assert!(detection::triangles_intersect([H, I, G], PQR).is_some());
     assert!(detection::triangles_intersect(GHI, MNO).is_none());
     assert!(detection::triangles_intersect(GHI, PQR).is_some());
-    assert!(detection::triangles_intersect(GHI, STU).is_none());
+    // STU lies inside GHI, which counts as intersecting it either way round
+    assert!(detection::triangles_intersect(GHI, STU).is_some());
+    assert!(detection::triangles_intersect(STU, GHI).is_some());
     assert!(detection::triangles_intersect(GHI, [J, L, Q]).is_some());
     assert!(detection::triangles_intersect(JKL, PQR).is_none());
     assert!(detection::triangles_intersect(MNO, VWZ).is_none());